@@ -0,0 +1,45 @@
+//! Generates the list of solutions that the main binary compiles in, so that `cargo all` and
+//! `cargo time` can run them in-process. Every day keeps its own binary in `src/bin`.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+
+    let mut days = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let day = name.strip_suffix(".rs")?;
+            (day.len() == 2 && day.bytes().all(|b| b.is_ascii_digit())).then(|| day.to_string())
+        })
+        .collect::<Vec<_>>();
+    days.sort();
+
+    let mut code = String::new();
+
+    for day in &days {
+        let path = bin_dir.join(format!("{day}.rs"));
+        code.push_str(&format!(
+            "#[path = {:?}]\nmod day_{day};\n",
+            path.display().to_string()
+        ));
+    }
+
+    code.push_str("\npub static SOLUTIONS: &[&dyn advent_of_code::template::Solution] = &[\n");
+    for day in &days {
+        code.push_str(&format!("    &day_{day}::SOLUTION,\n"));
+    }
+    code.push_str("];\n");
+
+    // each day's `main` only runs in its own binary.
+    code.push_str(&format!("\nconst _: [fn(); {}] = [\n", days.len()));
+    for day in &days {
+        code.push_str(&format!("    day_{day}::main,\n"));
+    }
+    code.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, code).unwrap();
+}
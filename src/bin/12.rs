@@ -1,36 +1,93 @@
-use advent_of_code::PrototypeComputer;
+use advent_of_code::{Backend, ClosedForm, Config, Interval, PrototypeComputer};
 
 advent_of_code::solution!(12);
 
-pub fn part_one(input: &str) -> Option<i32> {
+fn computer(backend: Backend) -> PrototypeComputer {
+    PrototypeComputer::with_config(&Config {
+        backend,
+        ..Config::default()
+    })
+}
+
+/// The program's result for any initial value of `c`.
+fn closed_form(input: &str, backend: Backend) -> ClosedForm {
     let program = input.lines().collect::<Vec<_>>();
 
-    let mut computer = PrototypeComputer::new();
+    let mut computer = computer(backend);
 
-    computer.run_program(&program);
+    computer.load_program(&program).unwrap();
 
-    computer.get_register('a')
+    computer.closed_form(&[('c', Interval::full(computer.arithmetic()))])
 }
 
-pub fn part_two(input: &str) -> Option<i32> {
-    let program = input.lines().collect::<Vec<_>>();
+/// Register `a` after running the program with `c` initialized to `c`. Runs the program if
+/// its closed form gave up on this value.
+fn solve(input: &str, c: i64, backend: Backend) -> Option<i64> {
+    if let Some(registers) = closed_form(input, backend).evaluate(&[('c', c)]) {
+        return registers.get('a');
+    }
 
-    let mut computer = PrototypeComputer::new();
+    let program = input.lines().collect::<Vec<_>>();
 
-    computer.set_register('c', 1);
+    let mut computer = computer(backend);
 
-    computer.run_program(&program);
+    computer.set_register('c', c).unwrap();
+    computer.run_program(&program).unwrap();
 
     computer.get_register('a')
 }
 
+pub fn part_one(input: &str, backend: Backend) -> Option<i64> {
+    solve(input, 0, backend)
+}
+
+pub fn part_two(input: &str, backend: Backend) -> Option<i64> {
+    solve(input, 1, backend)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::ProgramBuilder;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY),
+            Backend::default(),
+        );
         assert_eq!(result, Some(42));
     }
+
+    #[test]
+    fn test_part_two() {
+        // increments `a` a second time only when `c` is set.
+        let program = ProgramBuilder::new()
+            .inc('a')
+            .jnz('c', 2)
+            .jnz(1, 2)
+            .inc('a')
+            .to_source();
+
+        let backend = Backend::default();
+        assert_eq!(part_one(&program, backend), Some(1));
+        assert_eq!(part_two(&program, backend), Some(2));
+    }
+
+    #[test]
+    fn runs_programs_without_closed_form() {
+        // counts down for more steps than symbolic execution takes on a single path.
+        let program = ProgramBuilder::new()
+            .cpy('c', 'a')
+            .inc('a')
+            .cpy(600_000, 'b')
+            .dec('b')
+            .jnz('b', -1)
+            .to_source();
+
+        assert!(!closed_form(&program, Backend::default()).is_complete());
+        let backend = Backend::default();
+        assert_eq!(part_one(&program, backend), Some(1));
+        assert_eq!(part_two(&program, backend), Some(2));
+    }
 }
@@ -33,7 +33,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         let secret = make_secret(salt, i);
         let hash = make_hash(&secret);
 
-        triples.retain(|&(_, idx, is_key)| is_key || idx > if i >= 1000 { i - 1000 } else { 0 });
+        triples.retain(|&(_, idx, is_key)| is_key || idx > i.saturating_sub(1000));
 
         for (triple, _, is_key) in triples.iter_mut().filter(|(_, _, is_key)| !is_key) {
             let triple: &str = triple;
@@ -69,7 +69,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         let secret = advent_of_code::make_secret(salt, i);
         let hash = make_key_stretching_hash(&secret);
 
-        triples.retain(|&(_, idx, is_key)| is_key || idx > if i >= 1000 { i - 1000 } else { 0 });
+        triples.retain(|&(_, idx, is_key)| is_key || idx > i.saturating_sub(1000));
 
         for (triple, _, is_key) in triples.iter_mut().filter(|(_, _, is_key)| !is_key) {
             let triple: &str = triple;
@@ -1,48 +1,37 @@
-use advent_of_code::PrototypeComputer;
+use advent_of_code::{Arithmetic, Backend, Config, Overflow, PrototypeComputer, Width};
 
 advent_of_code::solution!(23);
 
-pub fn part_one(input: &str) -> Option<i32> {
+pub fn part_one(input: &str, backend: Backend) -> Option<i64> {
     let program = input.lines().collect::<Vec<_>>();
 
-    let mut computer = PrototypeComputer::new();
+    let mut computer = PrototypeComputer::with_config(&Config {
+        backend,
+        ..Config::default()
+    });
 
-    computer.set_register('a', 7);
+    computer.set_register('a', 7).unwrap();
 
-    computer.run_program(&program);
+    computer.run_program(&program).unwrap();
 
     computer.get_register('a')
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let computer = PrototypeComputer::new();
-    let program = computer.parse_program(&input.lines().collect::<Vec<_>>());
-
-    let num: u64 = program
-        .iter()
-        .filter_map(|i| {
-            if i.name != "cpy" && i.name != "jnz" {
-                return None;
-            }
-
-            let val = i.arguments[0].parse::<u64>();
-            if val.is_err() {
-                return None;
-            }
+pub fn part_two(input: &str, backend: Backend) -> Option<i64> {
+    let program = input.lines().collect::<Vec<_>>();
 
-            let val = val.unwrap();
+    let mut computer = PrototypeComputer::with_config(&Config {
+        arithmetic: Arithmetic::new(Width::I64, Overflow::Checked),
+        backend,
+        ..Config::default()
+    });
 
-            if val > 90 {
-                Some(val)
-            } else {
-                None
-            }
-        })
-        .product();
+    computer.set_optimizations(true);
+    computer.set_register('a', 12).unwrap();
 
-    let factorial: u64 = (1..=12).product();
+    computer.run_program(&program).unwrap();
 
-    Some(num + factorial)
+    computer.get_register('a')
 }
 
 #[cfg(test)]
@@ -56,7 +45,7 @@ mod tests {
 
         let mut computer = PrototypeComputer::new();
 
-        computer.run_program(&program);
+        computer.run_program(&program).unwrap();
 
         let result = computer.get_register('a');
         assert_eq!(result, Some(3));
@@ -112,7 +112,7 @@ fn parse_map(input: &str) -> Option<Map> {
         }
     }
 
-    map.poi.sort_by(|a, b| a.id.cmp(&b.id));
+    map.poi.sort_by_key(|a| a.id);
 
     map.width = max_x as i32 + 1;
     map.height = max_y as i32 + 1;
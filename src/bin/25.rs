@@ -1,35 +1,59 @@
-use advent_of_code::PrototypeComputer;
+use advent_of_code::{Backend, Budget, Config, PrototypeComputer};
 
 advent_of_code::solution!(25);
 
-pub fn part_one(input: &str) -> Option<i32> {
-    let program = input.lines().collect::<Vec<_>>();
+/// Upper bound of values to inspect before giving up on finding a repeating output stream.
+const MAX_OUTPUTS: usize = 10_000;
 
-    for i in 0.. {
-        let mut computer = PrototypeComputer::new();
-        computer.load_program(&program);
-        computer.set_register('a', i);
+/// Upper bound of instructions to execute per candidate, so a non-terminating search without
+/// output cannot hang.
+const MAX_STEPS: u64 = 10_000_000;
 
-        let mut expected = 0;
-        let mut is_valid = true;
+/// Number of values checked before the more expensive cycle search. Must be even, so the
+/// remaining stream starts with `0` again.
+const QUICK_CHECK_OUTPUTS: usize = 16;
 
-        for _ in 0..10 {
-            let result = computer.run_to_next_output();
+/// The clock signal `0, 1, 0, 1, ...`.
+fn clock_signal() -> impl Iterator<Item = i64> {
+    [0, 1].into_iter().cycle()
+}
 
-            if result != expected {
-                is_valid = false;
-                break;
-            }
+/// Returns the period of the output stream if it provably is the clock signal `0, 1, 0, 1, ...`.
+fn clock_signal_period(program: &[&str], a: i64, backend: Backend) -> Option<usize> {
+    let mut computer = PrototypeComputer::with_config(&Config {
+        backend,
+        ..Config::default()
+    });
+    computer.load_program(program).unwrap();
+    computer.set_register('a', a).unwrap();
 
-            expected = (expected + 1).rem_euclid(2);
-        }
+    let starts_with_clock_signal = computer
+        .outputs(Budget::steps(MAX_STEPS))
+        .take(QUICK_CHECK_OUTPUTS)
+        .eq(clock_signal().take(QUICK_CHECK_OUTPUTS));
 
-        if is_valid {
-            return Some(i);
-        }
+    if !starts_with_clock_signal {
+        return None;
     }
 
-    None
+    let cycle = computer.find_output_cycle(MAX_OUTPUTS, Budget::steps(MAX_STEPS))?;
+    let len = cycle.prefix.len() + cycle.period.len();
+
+    let is_clock_signal = cycle.period.len() % 2 == 0
+        && cycle
+            .prefix
+            .iter()
+            .chain(&cycle.period)
+            .copied()
+            .eq(clock_signal().take(len));
+
+    is_clock_signal.then_some(cycle.period.len())
+}
+
+pub fn part_one(input: &str, backend: Backend) -> Option<i64> {
+    let program = input.lines().collect::<Vec<_>>();
+
+    (0..).find(|&a| clock_signal_period(&program, a, backend).is_some())
 }
 
 pub fn part_two(_input: &str) -> Option<u32> {
@@ -39,6 +63,31 @@ pub fn part_two(_input: &str) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::ProgramBuilder;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY),
+            Backend::default(),
+        );
+        assert_eq!(result, Some(2));
+    }
+
+    #[test]
+    fn test_clock_signal_period() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let program = input.lines().collect::<Vec<_>>();
+
+        let backend = Backend::default();
+        assert_eq!(clock_signal_period(&program, 2, backend), Some(2));
+        assert_eq!(clock_signal_period(&program, 3, backend), None);
+
+        let source = ProgramBuilder::new().out(0).out(1).jnz(1, -2).to_source();
+        let program = source.lines().collect::<Vec<_>>();
+
+        assert_eq!(clock_signal_period(&program, 0, backend), Some(2));
+    }
 
     #[test]
     fn test_part_two() {
@@ -0,0 +1,249 @@
+//! An interactive assembunny shell. Instructions typed at the prompt are appended to the
+//! program and run right away; commands starting with `:` inspect and control the computer.
+
+use std::fs;
+use std::io::{stdin, stdout, Write};
+use std::path::Path;
+
+use advent_of_code::template::{Day, ANSI_ITALIC, ANSI_RESET};
+use advent_of_code::{Budget, Halt, PrototypeComputer};
+
+/// Steps a typed instruction may run for, so that an accidental infinite loop returns control.
+const MAX_STEPS: u64 = 1_000_000;
+
+const HELP: &str = "\
+Type an instruction (e.g. `cpy 41 a`) to append it to the program and run it, or a command:
+  :load <day|path>   replace the program with a day's input or a file, resetting the computer
+  :run <n>           run at most n steps from the current instruction
+  :regs              print the register file
+  :set <reg> <value> change a register
+  :list              print the program, marking the current instruction
+  :reset             clear the program and all registers
+  :help              print this message
+  :quit              leave the shell";
+
+/// Whether the shell keeps reading commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Control {
+    Continue,
+    Quit,
+}
+
+struct Session {
+    computer: PrototypeComputer,
+}
+
+impl Session {
+    fn new() -> Self {
+        Self {
+            computer: PrototypeComputer::new(),
+        }
+    }
+
+    /// Handles one line of input, writing all feedback to `out`.
+    fn execute(&mut self, line: &str, out: &mut impl Write) -> std::io::Result<Control> {
+        let line = line.trim();
+        let mut words = line.split_whitespace();
+
+        match (words.next(), words.next(), words.next()) {
+            (None, ..) => {}
+            (Some(":q" | ":quit"), ..) => return Ok(Control::Quit),
+            (Some(":h" | ":help"), ..) => writeln!(out, "{HELP}")?,
+            (Some(":regs"), ..) => self.print_registers(out)?,
+            (Some(":list"), ..) => self.print_program(out)?,
+            (Some(":reset"), ..) => {
+                self.computer = PrototypeComputer::new();
+                writeln!(out, "Cleared program and registers.")?;
+            }
+            (Some(":load"), Some(source), None) => self.load(source, out)?,
+            (Some(":run"), Some(steps), None) => match steps.parse() {
+                Ok(steps) => self.run(Budget::steps(steps), out)?,
+                Err(_) => writeln!(out, "Expected a number of steps, got `{steps}`.")?,
+            },
+            (Some(":set"), Some(register), Some(val)) => {
+                let register = register.chars().next().filter(|_| register.len() == 1);
+                match (register, val.parse()) {
+                    (Some(register), Ok(val)) if self.computer.get_register(register).is_some() => {
+                        match self.computer.set_register(register, val) {
+                            Ok(()) => self.print_registers(out)?,
+                            Err(e) => writeln!(out, "{e}")?,
+                        }
+                    }
+                    _ => writeln!(out, "Usage: :set <reg> <value>")?,
+                }
+            }
+            (Some(command), ..) if command.starts_with(':') => {
+                writeln!(out, "Unknown or incomplete command `{line}`. Type `:help`.")?;
+            }
+            (Some(_), ..) => self.push(line, out)?,
+        }
+
+        Ok(Control::Continue)
+    }
+
+    fn push(&mut self, line: &str, out: &mut impl Write) -> std::io::Result<()> {
+        let instruction = match self.computer.parse_program(&[line]) {
+            Ok(program) => program[0],
+            Err(e) => return writeln!(out, "{}", e.reason),
+        };
+
+        if let Err(e) = self.computer.push_instruction(instruction) {
+            return writeln!(out, "{e}");
+        }
+
+        self.run(Budget::steps(MAX_STEPS), out)
+    }
+
+    fn load(&mut self, source: &str, out: &mut impl Write) -> std::io::Result<()> {
+        let path = match source.parse::<Day>() {
+            Ok(day) => Path::new("data").join("inputs").join(format!("{day}.txt")),
+            Err(_) => Path::new(source).to_path_buf(),
+        };
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => return writeln!(out, "Could not read {}: {e}", path.display()),
+        };
+
+        let mut computer = PrototypeComputer::new();
+        match computer.load_program(&text.lines().collect::<Vec<_>>()) {
+            Ok(()) => {
+                self.computer = computer;
+                writeln!(
+                    out,
+                    "Loaded {} instructions from {}.",
+                    self.computer.program().len(),
+                    path.display()
+                )
+            }
+            Err(e) => writeln!(out, "Failed to parse {}: {e}", path.display()),
+        }
+    }
+
+    /// Runs within the budget, printing every value sent and why the run stopped.
+    fn run(&mut self, budget: Budget, out: &mut impl Write) -> std::io::Result<()> {
+        let start = self.computer.steps();
+
+        let mut outputs = self.computer.outputs(budget);
+        for val in outputs.by_ref() {
+            writeln!(out, "out: {val}")?;
+        }
+        let halt = outputs.halt();
+
+        let steps = self.computer.steps() - start;
+        match halt {
+            Some(Halt::Exited) => {}
+            Some(Halt::BudgetExhausted) => writeln!(
+                out,
+                "Paused after {steps} steps at instruction {}. Use `:run <n>` to continue.",
+                self.computer.instruction_pointer()
+            )?,
+            Some(halt) => writeln!(out, "Stopped: {halt}.")?,
+            None => unreachable!("the output stream ended"),
+        }
+
+        self.print_registers(out)
+    }
+
+    fn print_registers(&self, out: &mut impl Write) -> std::io::Result<()> {
+        let registers = self
+            .computer
+            .registers()
+            .iter()
+            .map(|(r, v)| format!("{r}={v}"))
+            .collect::<Vec<_>>();
+
+        writeln!(out, "{}", registers.join(" "))
+    }
+
+    fn print_program(&self, out: &mut impl Write) -> std::io::Result<()> {
+        for (index, instruction) in self.computer.program().iter().enumerate() {
+            let marker = if index == self.computer.instruction_pointer() {
+                '>'
+            } else {
+                ' '
+            };
+            writeln!(out, "{marker} {index:>3}: {instruction}")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn main() {
+    let mut session = Session::new();
+
+    println!("🐰 Assembunny shell. Type `:help` for a list of commands.");
+
+    loop {
+        print!("{ANSI_ITALIC}bunny>{ANSI_RESET} ");
+        let _ = stdout().flush();
+
+        let mut line = String::new();
+        if stdin().read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+
+        match session.execute(&line, &mut stdout()) {
+            Ok(Control::Continue) => {}
+            Ok(Control::Quit) | Err(_) => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript(session: &mut Session, lines: &[&str]) -> String {
+        let mut out = Vec::new();
+        for line in lines {
+            session.execute(line, &mut out).unwrap();
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn runs_typed_instructions() {
+        let mut session = Session::new();
+        let output = transcript(&mut session, &["cpy 3 a", "inc b", "dec a", "jnz a -2"]);
+
+        assert_eq!(output.lines().last(), Some("a=0 b=3 c=0 d=0"));
+        assert_eq!(
+            transcript(&mut session, &[":list"]),
+            "    0: cpy 3 a\n    1: inc b\n    2: dec a\n    3: jnz a -2\n"
+        );
+    }
+
+    #[test]
+    fn runs_loaded_programs_in_bounded_steps() {
+        let mut session = Session::new();
+        let example = format!("data/examples/{}.txt", Day::new(12).unwrap());
+
+        let output = transcript(&mut session, &[&format!(":load {example}"), ":run 2"]);
+        assert!(output.contains("Paused after 2 steps at instruction 2."));
+
+        let output = transcript(&mut session, &[":run 1000", ":regs"]);
+        assert_eq!(output.lines().last(), Some("a=42 b=0 c=0 d=0"));
+
+        let output = transcript(&mut session, &[":reset", ":regs", ":list"]);
+        assert_eq!(output.lines().last(), Some("a=0 b=0 c=0 d=0"));
+    }
+
+    #[test]
+    fn reports_errors_without_changing_state() {
+        let mut session = Session::new();
+        let output = transcript(&mut session, &["mul a b", "inc e", ":run x", ":load"]);
+
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            [
+                "unknown opcode `mul`",
+                "`e` is neither a register nor an integer",
+                "Expected a number of steps, got `x`.",
+                "Unknown or incomplete command `:load`. Type `:help`.",
+            ]
+        );
+        assert!(session.computer.program().is_empty());
+    }
+}
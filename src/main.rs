@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, debug, disasm, download, read, scaffold, solve, time, verify,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -6,9 +8,19 @@ use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
+/// Every scaffolded day, compiled in as a module so that solutions can run in-process.
+/// Left out of tests, which already run with each day's binary, and of heap profiles, which need
+/// a single global allocator.
+#[cfg(not(any(test, feature = "dhat-heap")))]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
+    use advent_of_code::template::report::Format;
     use advent_of_code::template::Day;
     use std::process;
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
@@ -25,15 +37,33 @@ mod args {
             day: Day,
             release: bool,
             dhat: bool,
+            trace: bool,
             submit: Option<u8>,
+            format: Option<Format>,
         },
         All {
             release: bool,
+            format: Option<Format>,
+            jobs: usize,
+            timeout: Option<Duration>,
+        },
+        Debug {
+            day: Day,
+        },
+        Disasm {
+            day: Day,
+            dot: bool,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            interpreter: bool,
+            threshold: Option<f64>,
+            timeout: Option<Duration>,
+        },
+        Verify {
+            release: bool,
         },
         #[cfg(feature = "today")]
         Today,
@@ -45,17 +75,37 @@ mod args {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                format: args.opt_value_from_str("--format")?,
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                timeout: args
+                    .opt_value_from_str("--timeout")?
+                    .map(Duration::from_secs),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let interpreter = args.contains("--interpreter");
+                let threshold = args.opt_value_from_str("--threshold")?;
+                let timeout = args
+                    .opt_value_from_str("--timeout")?
+                    .map(Duration::from_secs);
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    interpreter,
+                    threshold,
+                    timeout,
                 }
             }
+            Some("debug") => AppArguments::Debug {
+                day: args.free_from_str()?,
+            },
+            Some("disasm") => AppArguments::Disasm {
+                day: args.free_from_str()?,
+                dot: args.contains("--dot"),
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -71,6 +121,11 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                trace: args.contains("--trace"),
+                format: args.opt_value_from_str("--format")?,
+            },
+            Some("verify") => AppArguments::Verify {
+                release: args.contains("--release"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -94,14 +149,31 @@ mod args {
 }
 
 fn main() {
+    #[cfg(not(any(test, feature = "dhat-heap")))]
+    advent_of_code::template::register(solutions::SOLUTIONS);
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                release,
+                format,
+                jobs,
+                timeout,
+            } => all::handle(release, format, jobs, timeout),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                interpreter,
+                threshold,
+                timeout,
+            } => time::handle(day, all, store, interpreter, threshold, timeout),
+            AppArguments::Debug { day } => debug::handle(day),
+            AppArguments::Disasm { day, dot } => disasm::handle(day, dot),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold { day, download } => {
@@ -114,8 +186,11 @@ fn main() {
                 day,
                 release,
                 dhat,
+                trace,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                format,
+            } => solve::handle(day, release, dhat, trace, submit, format),
+            AppArguments::Verify { release } => verify::handle(release),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
@@ -0,0 +1,480 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt::Display;
+
+use super::{
+    Arithmetic, Opcode, Operand, Overflow, PrototypeComputer, PrototypeComputerInstruction,
+    RegisterFile,
+};
+
+/// Number of times an instruction is revisited before its register ranges are widened, which
+/// guarantees that the analysis of a loop terminates.
+const WIDEN_AFTER: usize = 3;
+
+/// An inclusive range of values a register may hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval {
+    pub min: i64,
+    pub max: i64,
+}
+
+impl Interval {
+    pub fn new(min: i64, max: i64) -> Self {
+        Self { min, max }
+    }
+
+    pub fn constant(val: i64) -> Self {
+        Self::new(val, val)
+    }
+
+    /// Every value a register of the given arithmetic can hold.
+    pub fn full(arithmetic: Arithmetic) -> Self {
+        let (min, max) = arithmetic.width.bounds();
+        Self::new(min, max)
+    }
+
+    pub fn contains(self, val: i64) -> bool {
+        (self.min..=self.max).contains(&val)
+    }
+
+    fn join(self, other: Self) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    /// Pushes bounds that are still moving to the limits of the register width.
+    fn widen(self, next: Self, arithmetic: Arithmetic) -> Self {
+        let full = Self::full(arithmetic);
+        let min = if next.min < self.min {
+            full.min
+        } else {
+            self.min
+        };
+        let max = if next.max > self.max {
+            full.max
+        } else {
+            self.max
+        };
+
+        Self::new(min, max)
+    }
+
+    /// Adds `delta` to every value, following the overflow mode. Checked arithmetic faults on
+    /// overflow, so only the values that fit carry on.
+    fn shift(self, delta: i64, arithmetic: Arithmetic) -> Self {
+        let full = Self::full(arithmetic);
+        let min = i128::from(self.min) + i128::from(delta);
+        let max = i128::from(self.max) + i128::from(delta);
+
+        let fits = |val: i128| (i128::from(full.min)..=i128::from(full.max)).contains(&val);
+        if fits(min) && fits(max) {
+            return Self::new(arithmetic.fit(min).unwrap(), arithmetic.fit(max).unwrap());
+        }
+
+        match arithmetic.overflow {
+            Overflow::Wrapping => full,
+            Overflow::Checked | Overflow::Saturating => Self::new(
+                min.clamp(full.min.into(), full.max.into()) as i64,
+                max.clamp(full.min.into(), full.max.into()) as i64,
+            ),
+        }
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.min == self.max {
+            write!(f, "{}", self.min)
+        } else {
+            write!(f, "[{}, {}]", self.min, self.max)
+        }
+    }
+}
+
+/// How much a program may rewrite itself via `tgl`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SelfModification {
+    /// No `tgl` can hit an instruction of the program.
+    None,
+    /// Every `tgl` has a bounded offset, so the instructions it may rewrite are known.
+    Bounded,
+    /// Some `tgl` offset could not be bounded. Any instruction may be rewritten.
+    Unknown,
+}
+
+/// Abstract register ranges, indexed by register slot.
+type State = [Interval; 26];
+
+/// Which instructions `tgl` may rewrite, found by abstract interpretation of register ranges.
+///
+/// The result is sound: an instruction not reported as [toggleable](Self::may_be_toggled) is
+/// never rewritten, whatever path the program takes. Every instruction that may be toggled is
+/// analyzed in all the forms it can take.
+#[derive(Debug, Clone)]
+pub struct ToggleAnalysis {
+    offsets: Vec<Option<Interval>>,
+    targets: BTreeSet<usize>,
+    forms: Vec<BTreeSet<Opcode>>,
+    unbounded: bool,
+}
+
+impl ToggleAnalysis {
+    /// Analyzes a program started at its first instruction. Listed registers start anywhere
+    /// within their interval, all others at zero.
+    pub fn new(
+        program: &[PrototypeComputerInstruction],
+        arithmetic: Arithmetic,
+        registers: &[(char, Interval)],
+    ) -> Self {
+        let mut entry = [Interval::constant(0); 26];
+        for &(register, interval) in registers {
+            entry[RegisterFile::slot(register)] = interval;
+        }
+
+        Self::analyze(program, arithmetic, entry, 0)
+    }
+
+    fn analyze(
+        program: &[PrototypeComputerInstruction],
+        arithmetic: Arithmetic,
+        entry: State,
+        start: usize,
+    ) -> Self {
+        let mut analysis = Self {
+            offsets: vec![None; program.len()],
+            targets: BTreeSet::new(),
+            forms: program
+                .iter()
+                .map(|instruction| BTreeSet::from([instruction.opcode]))
+                .collect(),
+            unbounded: false,
+        };
+
+        // toggled instructions change the control flow, so repeat until their forms are stable.
+        loop {
+            analysis.offsets.fill(None);
+            analysis.run(program, arithmetic, entry, start);
+
+            let targets = analysis.find_targets(arithmetic);
+            let mut changed = false;
+
+            for &target in &targets {
+                let mut opcode = program[target].opcode;
+                while analysis.forms[target].insert(opcode.toggled()) {
+                    opcode = opcode.toggled();
+                    changed = true;
+                }
+            }
+
+            analysis.targets = targets;
+
+            if !changed {
+                return analysis;
+            }
+        }
+    }
+
+    fn find_targets(&mut self, arithmetic: Arithmetic) -> BTreeSet<usize> {
+        let full = Interval::full(arithmetic);
+        self.unbounded = false;
+
+        let len = self.offsets.len();
+        let mut targets = BTreeSet::new();
+
+        for (index, offset) in self.offsets.iter().enumerate() {
+            let Some(offset) = offset else { continue };
+            self.unbounded |= offset.min == full.min || offset.max == full.max;
+            targets
+                .extend((0..len).filter(|&target| offset.contains(target as i64 - index as i64)));
+        }
+
+        targets
+    }
+
+    /// Computes the register ranges at every instruction with a worklist, recording the offsets
+    /// of every reachable `tgl`.
+    fn run(
+        &mut self,
+        program: &[PrototypeComputerInstruction],
+        arithmetic: Arithmetic,
+        entry: State,
+        start: usize,
+    ) {
+        let mut states: Vec<Option<State>> = vec![None; program.len()];
+        let mut visits = vec![0; program.len()];
+        let mut worklist = VecDeque::new();
+
+        if start < program.len() {
+            states[start] = Some(entry);
+            worklist.push_back(start);
+        }
+
+        while let Some(index) = worklist.pop_front() {
+            let state = states[index].unwrap();
+            let instruction = program[index];
+
+            for opcode in self.forms[index].clone() {
+                for (next, next_state) in
+                    self.transfer(index, opcode, instruction, state, arithmetic)
+                {
+                    if next >= program.len() {
+                        continue;
+                    }
+
+                    let merged = match states[next] {
+                        None => next_state,
+                        Some(old) => {
+                            visits[next] += 1;
+                            let joined = join(&old, &next_state);
+                            let merged = if visits[next] > WIDEN_AFTER {
+                                widen(&old, &joined, arithmetic)
+                            } else {
+                                joined
+                            };
+
+                            if merged == old {
+                                continue;
+                            }
+                            merged
+                        }
+                    };
+
+                    states[next] = Some(merged);
+                    if !worklist.contains(&next) {
+                        worklist.push_back(next);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Executes one form of an instruction on abstract registers, returning every successor
+    /// with the register ranges along that edge. Jumps before the start of the program fault
+    /// and have no successor.
+    fn transfer(
+        &mut self,
+        index: usize,
+        opcode: Opcode,
+        instruction: PrototypeComputerInstruction,
+        mut state: State,
+        arithmetic: Arithmetic,
+    ) -> Vec<(usize, State)> {
+        let eval = |state: &State, operand: Operand| match operand {
+            Operand::Register(r) => state[RegisterFile::slot(r)],
+            Operand::Immediate(val) => Interval::constant(val),
+        };
+
+        // a form with the wrong number of operands faults.
+        if instruction.y.is_some() != (opcode.arity() == 2) {
+            return vec![];
+        }
+
+        match (opcode, instruction.x, instruction.y) {
+            (Opcode::Cpy, source, Some(Operand::Register(dest))) => {
+                state[RegisterFile::slot(dest)] = eval(&state, source);
+            }
+            (Opcode::Inc | Opcode::Dec, Operand::Register(r), _) => {
+                let delta = if opcode == Opcode::Inc { 1 } else { -1 };
+                let slot = RegisterFile::slot(r);
+                state[slot] = state[slot].shift(delta, arithmetic);
+            }
+            (Opcode::Jnz, condition, Some(skip)) => {
+                let value = eval(&state, condition);
+                let offsets = eval(&state, skip);
+                let mut successors = vec![];
+
+                if value.contains(0) {
+                    let mut fallthrough = state;
+                    if let Operand::Register(r) = condition {
+                        fallthrough[RegisterFile::slot(r)] = Interval::constant(0);
+                    }
+                    successors.push((index + 1, fallthrough));
+                }
+
+                if value != Interval::constant(0) {
+                    let mut jumped = state;
+                    if let Operand::Register(r) = condition {
+                        let slot = RegisterFile::slot(r);
+                        if value.min == 0 {
+                            jumped[slot].min = 1;
+                        } else if value.max == 0 {
+                            jumped[slot].max = -1;
+                        }
+                    }
+
+                    // jumps past the end exit, so only targets inside the program matter.
+                    let len = self.offsets.len();
+                    successors.extend(
+                        (0..len)
+                            .filter(|&target| offsets.contains(target as i64 - index as i64))
+                            .map(|target| (target, jumped)),
+                    );
+                }
+
+                return successors;
+            }
+            (Opcode::Tgl, target, _) => {
+                let offset = eval(&state, target);
+                let recorded = &mut self.offsets[index];
+                *recorded = Some(recorded.map_or(offset, |old| old.join(offset)));
+            }
+            (Opcode::Custom(definition), x, y) => {
+                // nothing is known beyond which registers it may write and what it may do.
+                for operand in [Some(x), y].into_iter().flatten() {
+                    if let Operand::Register(r) = operand {
+                        state[RegisterFile::slot(r)] = Interval::full(arithmetic);
+                    }
+                }
+
+                if definition.may_toggle() {
+                    self.offsets[index] = Some(Interval::full(arithmetic));
+                }
+
+                if definition.may_jump() {
+                    return (0..self.offsets.len())
+                        .chain([index + 1])
+                        .map(|target| (target, state))
+                        .collect();
+                }
+            }
+            _ => {}
+        }
+
+        vec![(index + 1, state)]
+    }
+
+    pub fn classification(&self) -> SelfModification {
+        if self.targets.is_empty() {
+            SelfModification::None
+        } else if self.unbounded {
+            SelfModification::Unknown
+        } else {
+            SelfModification::Bounded
+        }
+    }
+
+    /// The offsets the `tgl` at `index` may be executed with, or `None` if there is no
+    /// reachable `tgl` at `index`.
+    pub fn offsets(&self, index: usize) -> Option<Interval> {
+        self.offsets.get(index).copied().flatten()
+    }
+
+    /// Indices of the instructions some `tgl` may rewrite.
+    pub fn targets(&self) -> impl Iterator<Item = usize> + '_ {
+        self.targets.iter().copied()
+    }
+
+    /// Whether the `tgl` at `index` may rewrite an instruction. A `tgl` that is unreachable or
+    /// only ever points outside the program does nothing.
+    pub fn may_rewrite(&self, index: usize) -> bool {
+        let len = self.offsets.len();
+
+        self.offsets(index).is_some_and(|offset| {
+            (0..len).any(|target| offset.contains(target as i64 - index as i64))
+        })
+    }
+
+    pub fn may_be_toggled(&self, index: usize) -> bool {
+        self.targets.contains(&index)
+    }
+
+    /// Whether no instruction in `range` can ever be rewritten, so a fast path compiled from
+    /// it stays valid.
+    pub fn is_stable(&self, range: std::ops::Range<usize>) -> bool {
+        self.targets.range(range).next().is_none()
+    }
+}
+
+fn join(a: &State, b: &State) -> State {
+    std::array::from_fn(|slot| a[slot].join(b[slot]))
+}
+
+fn widen(old: &State, next: &State, arithmetic: Arithmetic) -> State {
+    std::array::from_fn(|slot| old[slot].widen(next[slot], arithmetic))
+}
+
+impl PrototypeComputer {
+    /// Analyzes the loaded program from the current instruction pointer and register values.
+    pub fn analyze_toggles(&self) -> ToggleAnalysis {
+        let mut entry = [Interval::constant(0); 26];
+        for (register, val) in self.registers.iter() {
+            entry[RegisterFile::slot(register)] = Interval::constant(val);
+        }
+
+        ToggleAnalysis::analyze(
+            &self.program,
+            self.arithmetic,
+            entry,
+            self.instruction_pointer,
+        )
+    }
+
+    /// Analyzes the loaded program from the current instruction pointer for any register
+    /// values, so the result holds whatever the registers are set to before running it.
+    pub(super) fn analyze_toggles_for_any_registers(&self) -> ToggleAnalysis {
+        let mut entry = [Interval::constant(0); 26];
+        for &register in self.registers.names() {
+            entry[RegisterFile::slot(register)] = Interval::full(self.arithmetic);
+        }
+
+        ToggleAnalysis::analyze(
+            &self.program,
+            self.arithmetic,
+            entry,
+            self.instruction_pointer,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze(program: &[&str], registers: &[(char, Interval)]) -> ToggleAnalysis {
+        let program = PrototypeComputer::new().parse_program(program).unwrap();
+        ToggleAnalysis::new(&program, Arithmetic::default(), registers)
+    }
+
+    #[test]
+    fn bounds_offsets_after_loops() {
+        let input = Interval::full(Arithmetic::default());
+        let analysis = analyze(
+            &["cpy a b", "dec b", "jnz b -1", "jnz 0 2", "tgl b"],
+            &[('a', input)],
+        );
+
+        assert_eq!(analysis.classification(), SelfModification::Bounded);
+        assert_eq!(analysis.offsets(4), Some(Interval::constant(0)));
+
+        let analysis = analyze(&["cpy 3 b", "dec b", "jnz b -1", "tgl 5"], &[]);
+        assert_eq!(analysis.classification(), SelfModification::None);
+        assert!(analysis.is_stable(0..4));
+    }
+
+    #[test]
+    fn follows_toggled_control_flow() {
+        // the example from day 23.
+        let analysis = analyze(
+            &[
+                "cpy 2 a", "tgl a", "tgl a", "tgl a", "cpy 1 a", "dec a", "dec a",
+            ],
+            &[],
+        );
+
+        assert_eq!(analysis.classification(), SelfModification::Bounded);
+        assert_eq!(analysis.targets().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(analysis.offsets(1), Some(Interval::constant(2)));
+        assert!(analysis.is_stable(0..3));
+        assert!(!analysis.may_be_toggled(6));
+    }
+
+    #[test]
+    fn gives_up_on_unbounded_offsets() {
+        let input = Interval::new(0, 100);
+        let analysis = analyze(
+            &["cpy a c", "dec c", "dec a", "jnz a -2", "tgl c"],
+            &[('a', input)],
+        );
+
+        assert_eq!(analysis.classification(), SelfModification::Unknown);
+        assert!(analysis.may_be_toggled(0));
+    }
+}
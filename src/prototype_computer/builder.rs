@@ -0,0 +1,148 @@
+use super::{Opcode, Operand, PrototypeComputerInstruction};
+
+impl From<char> for Operand {
+    fn from(register: char) -> Self {
+        Operand::Register(register)
+    }
+}
+
+impl From<i64> for Operand {
+    fn from(val: i64) -> Self {
+        Operand::Immediate(val)
+    }
+}
+
+impl From<i32> for Operand {
+    fn from(val: i32) -> Self {
+        Operand::Immediate(i64::from(val))
+    }
+}
+
+/// Renders a program as canonical assembunny source, one instruction per line.
+///
+/// Parsing the result with [`PrototypeComputer::parse_program`](super::PrototypeComputer::parse_program)
+/// yields the same program.
+pub fn to_source(program: &[PrototypeComputerInstruction]) -> String {
+    program
+        .iter()
+        .map(|instruction| format!("{instruction}\n"))
+        .collect()
+}
+
+/// Assembles a program instruction by instruction. Operands are registers (`'a'`) or
+/// immediates (`1`); jump and toggle offsets are relative, like in assembunny source.
+#[derive(Debug, Clone, Default)]
+pub struct ProgramBuilder {
+    program: Vec<PrototypeComputerInstruction>,
+}
+
+impl ProgramBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&mut self, opcode: Opcode, x: Operand, y: Option<Operand>) -> &mut Self {
+        self.program
+            .push(PrototypeComputerInstruction::new(opcode, x, y));
+        self
+    }
+
+    pub fn cpy(&mut self, source: impl Into<Operand>, dest: impl Into<Operand>) -> &mut Self {
+        self.push(Opcode::Cpy, source.into(), Some(dest.into()))
+    }
+
+    pub fn inc(&mut self, target: impl Into<Operand>) -> &mut Self {
+        self.push(Opcode::Inc, target.into(), None)
+    }
+
+    pub fn dec(&mut self, target: impl Into<Operand>) -> &mut Self {
+        self.push(Opcode::Dec, target.into(), None)
+    }
+
+    pub fn jnz(&mut self, condition: impl Into<Operand>, offset: impl Into<Operand>) -> &mut Self {
+        self.push(Opcode::Jnz, condition.into(), Some(offset.into()))
+    }
+
+    pub fn tgl(&mut self, offset: impl Into<Operand>) -> &mut Self {
+        self.push(Opcode::Tgl, offset.into(), None)
+    }
+
+    pub fn out(&mut self, source: impl Into<Operand>) -> &mut Self {
+        self.push(Opcode::Out, source.into(), None)
+    }
+
+    /// Number of instructions so far, i.e. the index of the next one.
+    pub fn len(&self) -> usize {
+        self.program.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.program.is_empty()
+    }
+
+    pub fn build(&self) -> Vec<PrototypeComputerInstruction> {
+        self.program.clone()
+    }
+
+    pub fn to_source(&self) -> String {
+        to_source(&self.program)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Budget, Halt, PrototypeComputer, PrototypeComputerError};
+
+    #[test]
+    fn builds_programs() {
+        let program = ProgramBuilder::new()
+            .cpy(41, 'a')
+            .inc('a')
+            .dec('a')
+            .jnz('a', 2)
+            .dec('a')
+            .build();
+
+        let mut computer = PrototypeComputer::new();
+        computer.load_instructions(program).unwrap();
+
+        assert_eq!(computer.run(Budget::unlimited()), Halt::Exited);
+        assert_eq!(computer.get_register('a'), Some(41));
+    }
+
+    #[test]
+    fn round_trips_through_source() {
+        let computer = PrototypeComputer::new();
+        let operands = ['a', 'd']
+            .map(Operand::from)
+            .into_iter()
+            .chain([-3, 0, 7].map(Operand::from));
+
+        let mut builder = ProgramBuilder::new();
+        for x in operands.clone() {
+            builder.inc(x).dec(x).tgl(x).out(x);
+            for y in operands.clone() {
+                builder.cpy(x, y).jnz(x, y);
+            }
+        }
+
+        let program = builder.build();
+        let source = builder.to_source();
+        let lines = source.lines().collect::<Vec<_>>();
+
+        assert_eq!(computer.parse_program(&lines).unwrap(), program);
+        assert_eq!(lines[..4], ["inc a", "dec a", "tgl a", "out a"]);
+    }
+
+    #[test]
+    fn rejects_unknown_registers() {
+        let mut computer = PrototypeComputer::new();
+        let program = ProgramBuilder::new().inc('e').build();
+
+        assert_eq!(
+            computer.load_instructions(program),
+            Err(PrototypeComputerError::UnknownRegister('e'))
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use super::{Opcode, Operand, PrototypeComputerInstruction, RegisterFile, ToggleAnalysis};
+
+/// How a [`PrototypeComputer`](super::PrototypeComputer) executes instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Backend {
+    /// Decodes every instruction each time it is executed. Serves as the reference.
+    Interpreter,
+    /// Executes pre-decoded instructions with register slots and jump targets resolved.
+    /// Instructions rewritten by `tgl` are recompiled.
+    #[default]
+    Compiled,
+}
+
+impl Backend {
+    /// Looks up a backend by its name, e.g. `"interpreter"`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "interpreter" => Some(Self::Interpreter),
+            "compiled" => Some(Self::Compiled),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Backend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Interpreter => "interpreter",
+            Self::Compiled => "compiled",
+        })
+    }
+}
+
+/// A pre-decoded instruction. Registers are resolved to slots and jumps to absolute targets;
+/// a target past the end of the program exits it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Op {
+    CpyImm {
+        val: i64,
+        dest: usize,
+    },
+    CpyReg {
+        src: usize,
+        dest: usize,
+    },
+    Inc(usize),
+    Dec(usize),
+    Jmp(usize),
+    JnzReg {
+        cond: usize,
+        target: usize,
+    },
+    /// Does nothing, e.g. `jnz 0 x` or an instruction made invalid by `tgl`.
+    Nop,
+    /// Anything rare enough to leave to the interpreter: `tgl` that may rewrite an instruction,
+    /// `out`, jumps by a register or before the start of the program, and malformed
+    /// instructions.
+    Interpret,
+}
+
+impl Op {
+    fn compile(index: usize, instruction: &PrototypeComputerInstruction) -> Self {
+        let slot = RegisterFile::slot;
+        let target = |offset: i64| {
+            isize::try_from(offset)
+                .ok()
+                .and_then(|offset| index.checked_add_signed(offset))
+        };
+
+        match (instruction.opcode, instruction.x, instruction.y) {
+            (Opcode::Cpy, Operand::Immediate(val), Some(Operand::Register(dest))) => Op::CpyImm {
+                val,
+                dest: slot(dest),
+            },
+            (Opcode::Cpy, Operand::Register(src), Some(Operand::Register(dest))) => Op::CpyReg {
+                src: slot(src),
+                dest: slot(dest),
+            },
+            (Opcode::Cpy, _, Some(Operand::Immediate(_))) => Op::Nop,
+            (Opcode::Inc, Operand::Register(r), None) => Op::Inc(slot(r)),
+            (Opcode::Dec, Operand::Register(r), None) => Op::Dec(slot(r)),
+            (Opcode::Inc | Opcode::Dec, Operand::Immediate(_), None) => Op::Nop,
+            (Opcode::Jnz, Operand::Immediate(0), Some(_)) => Op::Nop,
+            (Opcode::Jnz, cond, Some(Operand::Immediate(offset))) => match (cond, target(offset)) {
+                (Operand::Immediate(_), Some(target)) => Op::Jmp(target),
+                (Operand::Register(cond), Some(target)) => Op::JnzReg {
+                    cond: slot(cond),
+                    target,
+                },
+                (_, None) => Op::Interpret,
+            },
+            _ => Op::Interpret,
+        }
+    }
+}
+
+/// The compiled form of a program, kept in sync with `tgl` rewrites.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Compiled {
+    ops: Vec<Op>,
+}
+
+impl Compiled {
+    /// Compiles `program`. A `tgl` that `toggles` proves never to rewrite an instruction
+    /// compiles to a [`Op::Nop`] instead of being left to the interpreter.
+    pub(super) fn new(program: &[PrototypeComputerInstruction], toggles: &ToggleAnalysis) -> Self {
+        Self {
+            ops: program
+                .iter()
+                .enumerate()
+                .map(|(index, instruction)| match instruction.opcode {
+                    Opcode::Tgl if instruction.y.is_none() && !toggles.may_rewrite(index) => {
+                        Op::Nop
+                    }
+                    _ => Op::compile(index, instruction),
+                })
+                .collect(),
+        }
+    }
+
+    pub(super) fn op_at(&self, index: usize) -> Op {
+        self.ops[index]
+    }
+
+    /// Recompiles a single instruction after `tgl` rewrote it. Ops only depend on their own
+    /// instruction and index, so nothing else needs to change.
+    pub(super) fn recompile(&mut self, program: &[PrototypeComputerInstruction], index: usize) {
+        self.ops[index] = Op::compile(index, &program[index]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PrototypeComputer;
+
+    #[test]
+    fn resolves_operands_and_targets() {
+        let computer = PrototypeComputer::new();
+        let arithmetic = computer.arithmetic();
+        let program = computer
+            .parse_program(&[
+                "cpy 41 a", "cpy a b", "inc c", "jnz b -1", "jnz 1 9", "jnz 0 a", "jnz a -9",
+                "out a",
+            ])
+            .unwrap();
+
+        let compiled = Compiled::new(&program, &ToggleAnalysis::new(&program, arithmetic, &[]));
+        let ops = (0..program.len())
+            .map(|i| compiled.op_at(i))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            ops,
+            vec![
+                Op::CpyImm { val: 41, dest: 0 },
+                Op::CpyReg { src: 0, dest: 1 },
+                Op::Inc(2),
+                Op::JnzReg { cond: 1, target: 2 },
+                Op::Jmp(13),
+                Op::Nop,
+                Op::Interpret,
+                Op::Interpret,
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_tgl_that_cannot_rewrite_an_instruction() {
+        let mut computer = PrototypeComputer::new();
+        computer
+            .load_program(&["tgl 5", "tgl 1", "dec a", "tgl a"])
+            .unwrap();
+
+        let toggles = computer.analyze_toggles_for_any_registers();
+        let ops = (0..4)
+            .map(|i| Compiled::new(&computer.program, &toggles).op_at(i))
+            .collect::<Vec<_>>();
+
+        assert_eq!(ops, vec![Op::Nop, Op::Interpret, Op::Dec(0), Op::Interpret]);
+    }
+
+    #[test]
+    fn matches_interpreter_on_toggled_programs() {
+        let program = [
+            "cpy 2 a", "tgl a", "tgl a", "tgl a", "cpy 1 a", "dec a", "dec a", "out a",
+        ];
+
+        let outputs = [Backend::Interpreter, Backend::Compiled].map(|backend| {
+            let mut computer = PrototypeComputer::new();
+            computer.set_backend(backend);
+            computer.load_program(&program).unwrap();
+
+            let outputs = computer
+                .outputs(crate::Budget::unlimited())
+                .collect::<Vec<_>>();
+            (outputs, computer.get_register('a'), computer.steps())
+        });
+
+        assert_eq!(outputs[0], outputs[1]);
+        assert_eq!(outputs[1].1, Some(3));
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::BTreeSet;
+
+use super::{Halt, PrototypeComputer, PrototypeComputerInstruction};
+
+/// Why the [`Debugger`] handed control back to the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    Stepped,
+    Breakpoint(usize),
+    Watchpoint {
+        register: char,
+        old: i64,
+        new: i64,
+    },
+    /// The instruction [faulted](Halt::is_fault); the instruction pointer stays on it.
+    Fault(Halt),
+    Halted,
+}
+
+/// Drives a [`PrototypeComputer`] one instruction at a time, stopping on breakpoints
+/// (instruction indices) and watchpoints (register changes).
+#[derive(Debug)]
+pub struct Debugger {
+    pub computer: PrototypeComputer,
+    original: Vec<PrototypeComputerInstruction>,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeSet<char>,
+    outputs: Vec<i64>,
+}
+
+impl Debugger {
+    pub fn new(computer: PrototypeComputer) -> Self {
+        Self {
+            original: computer.program().to_vec(),
+            computer,
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeSet::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Adds a breakpoint, or removes it if already set. Returns whether it is now set.
+    pub fn toggle_breakpoint(&mut self, index: usize) -> bool {
+        !self.breakpoints.remove(&index) && self.breakpoints.insert(index)
+    }
+
+    /// Adds a watchpoint, or removes it if already set. Returns whether it is now set.
+    pub fn toggle_watchpoint(&mut self, register: char) -> bool {
+        !self.watchpoints.remove(&register) && self.watchpoints.insert(register)
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = &usize> {
+        self.breakpoints.iter()
+    }
+
+    pub fn watchpoints(&self) -> impl Iterator<Item = &char> {
+        self.watchpoints.iter()
+    }
+
+    /// Values sent via `out` so far.
+    pub fn outputs(&self) -> &[i64] {
+        &self.outputs
+    }
+
+    /// The instruction at `index` as it was loaded, if `tgl` has rewritten it since.
+    pub fn toggled_from(&self, index: usize) -> Option<&PrototypeComputerInstruction> {
+        let original = self.original.get(index)?;
+        (Some(original) != self.computer.program().get(index)).then_some(original)
+    }
+
+    /// Executes a single instruction.
+    pub fn step(&mut self) -> StopReason {
+        if self.computer.is_halted() {
+            return StopReason::Halted;
+        }
+
+        let watched = self.watched_values();
+
+        match self.computer.step() {
+            Ok(Some(val)) => self.outputs.push(val),
+            Ok(None) => {}
+            Err(Halt::Exited) => return StopReason::Halted,
+            Err(halt) => return StopReason::Fault(halt),
+        }
+
+        let changed = self
+            .watched_values()
+            .into_iter()
+            .zip(watched)
+            .find(|((_, new), (_, old))| new != old);
+
+        if let Some(((register, new), (_, old))) = changed {
+            StopReason::Watchpoint { register, old, new }
+        } else if self.computer.is_halted() {
+            StopReason::Halted
+        } else if self
+            .breakpoints
+            .contains(&self.computer.instruction_pointer())
+        {
+            StopReason::Breakpoint(self.computer.instruction_pointer())
+        } else {
+            StopReason::Stepped
+        }
+    }
+
+    /// Executes instructions until a breakpoint or watchpoint is hit, an instruction faults
+    /// or the program halts.
+    pub fn resume(&mut self) -> StopReason {
+        loop {
+            match self.step() {
+                StopReason::Stepped => {}
+                reason => return reason,
+            }
+        }
+    }
+
+    fn watched_values(&self) -> Vec<(char, i64)> {
+        self.watchpoints
+            .iter()
+            .filter_map(|&r| Some((r, self.computer.get_register(r)?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn debugger(program: &[&str]) -> Debugger {
+        let mut computer = PrototypeComputer::new();
+        computer.load_program(program).unwrap();
+        Debugger::new(computer)
+    }
+
+    #[test]
+    fn stops_on_breakpoints_and_watchpoints() {
+        let mut debugger = debugger(&["cpy 2 a", "dec a", "inc b", "jnz a -2"]);
+
+        assert!(debugger.toggle_breakpoint(2));
+        assert_eq!(debugger.resume(), StopReason::Breakpoint(2));
+        assert_eq!(debugger.resume(), StopReason::Breakpoint(2));
+
+        assert!(!debugger.toggle_breakpoint(2));
+        assert!(debugger.toggle_watchpoint('b'));
+        assert_eq!(
+            debugger.resume(),
+            StopReason::Watchpoint {
+                register: 'b',
+                old: 1,
+                new: 2
+            }
+        );
+        assert_eq!(debugger.resume(), StopReason::Halted);
+    }
+
+    #[test]
+    fn reports_toggled_instructions() {
+        let mut debugger = debugger(&["tgl 1", "inc a", "out a"]);
+
+        assert_eq!(debugger.toggled_from(1), None);
+        assert_eq!(debugger.resume(), StopReason::Halted);
+        assert_eq!(debugger.toggled_from(1).unwrap().to_string(), "inc a");
+        assert_eq!(debugger.computer.program()[1].to_string(), "dec a");
+        assert_eq!(debugger.outputs(), &[-1]);
+    }
+}
@@ -0,0 +1,421 @@
+use std::collections::BTreeSet;
+use std::fmt::{Display, Write};
+
+use super::{Opcode, Operand, ParseError, PrototypeComputer, PrototypeComputerInstruction};
+
+/// Where control goes after an instruction, as far as can be told without running it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Target {
+    Instruction(usize),
+    /// Past the end of the program, which halts it.
+    Exit,
+    /// A jump before the start of the program, which faults.
+    Invalid,
+    /// Depends on a register value.
+    Dynamic,
+}
+
+impl Target {
+    fn resolve(len: usize, index: usize, offset: i64) -> Self {
+        match index.checked_add_signed(offset as isize) {
+            Some(target) if target < len => Target::Instruction(target),
+            Some(_) => Target::Exit,
+            None => Target::Invalid,
+        }
+    }
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::Instruction(index) => write!(f, "{index}"),
+            Target::Exit => write!(f, "exit"),
+            Target::Invalid => write!(f, "invalid"),
+            Target::Dynamic => write!(f, "?"),
+        }
+    }
+}
+
+/// A maximal run of instructions that is only entered at its first instruction and only left
+/// after its last one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BasicBlock {
+    pub start: usize,
+    /// Exclusive end index.
+    pub end: usize,
+    pub successors: Vec<Target>,
+}
+
+impl BasicBlock {
+    pub fn contains(&self, index: usize) -> bool {
+        (self.start..self.end).contains(&index)
+    }
+}
+
+/// A parsed program annotated with its control flow, built from the program as loaded, i.e.
+/// before any `tgl` rewrote it.
+#[derive(Debug, Clone)]
+pub struct Disassembly {
+    program: Vec<PrototypeComputerInstruction>,
+    jumps: Vec<Option<Target>>,
+    toggles: Vec<Option<Target>>,
+    blocks: Vec<BasicBlock>,
+    loop_headers: BTreeSet<usize>,
+}
+
+impl Disassembly {
+    pub fn new(program: &[PrototypeComputerInstruction]) -> Self {
+        let len = program.len();
+
+        let relative = |index: usize, operand: Operand| match operand {
+            Operand::Immediate(offset) => Target::resolve(len, index, offset),
+            Operand::Register(_) => Target::Dynamic,
+        };
+
+        let jumps = program
+            .iter()
+            .enumerate()
+            .map(|(index, instruction)| match instruction.opcode {
+                Opcode::Jnz if instruction.x == Operand::Immediate(0) => None,
+                Opcode::Jnz => instruction.y.map(|skip| relative(index, skip)),
+                Opcode::Custom(definition) if definition.may_jump() => Some(Target::Dynamic),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let toggles = program
+            .iter()
+            .enumerate()
+            .map(|(index, instruction)| match instruction.opcode {
+                Opcode::Tgl => Some(relative(index, instruction.x)),
+                Opcode::Custom(definition) if definition.may_toggle() => Some(Target::Dynamic),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let loop_headers = jumps
+            .iter()
+            .enumerate()
+            .filter_map(|(index, jump)| match jump {
+                Some(Target::Instruction(target)) if *target <= index => Some(*target),
+                _ => None,
+            })
+            .collect();
+
+        let blocks = Self::split_blocks(program, &jumps);
+
+        Self {
+            program: program.to_vec(),
+            jumps,
+            toggles,
+            blocks,
+            loop_headers,
+        }
+    }
+
+    fn split_blocks(
+        program: &[PrototypeComputerInstruction],
+        jumps: &[Option<Target>],
+    ) -> Vec<BasicBlock> {
+        let mut leaders = BTreeSet::from([0]);
+
+        for (index, jump) in jumps.iter().enumerate() {
+            if let Some(target) = jump {
+                leaders.insert(index + 1);
+                if let Target::Instruction(target) = target {
+                    leaders.insert(*target);
+                }
+            }
+        }
+
+        leaders.retain(|&leader| leader < program.len());
+
+        let mut bounds = leaders.into_iter().collect::<Vec<_>>();
+        bounds.push(program.len());
+
+        bounds
+            .windows(2)
+            .map(|bound| {
+                let (start, end) = (bound[0], bound[1]);
+                let last = end - 1;
+
+                let fallthrough = if end < program.len() {
+                    Target::Instruction(end)
+                } else {
+                    Target::Exit
+                };
+
+                let instruction = &program[last];
+
+                let successors = match (instruction.opcode, instruction.x, jumps[last]) {
+                    // a `jnz` with an immediate condition other than zero always jumps.
+                    (Opcode::Jnz, Operand::Immediate(_), Some(target)) => vec![target],
+                    // custom jumps may fall through whatever their operands.
+                    (_, _, Some(target)) => vec![target, fallthrough],
+                    (_, _, None) => vec![fallthrough],
+                };
+
+                BasicBlock {
+                    start,
+                    end,
+                    successors,
+                }
+            })
+            .collect()
+    }
+
+    pub fn program(&self) -> &[PrototypeComputerInstruction] {
+        &self.program
+    }
+
+    pub fn blocks(&self) -> &[BasicBlock] {
+        &self.blocks
+    }
+
+    /// The block containing an instruction.
+    pub fn block_of(&self, index: usize) -> Option<&BasicBlock> {
+        self.blocks.iter().find(|block| block.contains(index))
+    }
+
+    /// Where a `jnz` goes when it jumps, or `None` for any other instruction and for `jnz 0 _`.
+    pub fn jump_target(&self, index: usize) -> Option<Target> {
+        self.jumps.get(index).copied().flatten()
+    }
+
+    /// Targets of backward jumps.
+    pub fn loop_headers(&self) -> impl Iterator<Item = usize> + '_ {
+        self.loop_headers.iter().copied()
+    }
+
+    /// The instruction a `tgl` toggles, or `None` for any other instruction.
+    pub fn toggle_target(&self, index: usize) -> Option<Target> {
+        self.toggles.get(index).copied().flatten()
+    }
+
+    /// Indices of the `tgl` instructions that could rewrite the instruction at `index`.
+    pub fn toggled_by(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.toggles
+            .iter()
+            .enumerate()
+            .filter(move |(_, toggle)| {
+                matches!(toggle, Some(Target::Dynamic))
+                    || *toggle == &Some(Target::Instruction(index))
+            })
+            .map(|(tgl, _)| tgl)
+    }
+
+    /// Renders the control-flow graph in Graphviz DOT format, one node per basic block.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph assembunny {\n");
+        dot.push_str("    node [shape=box, fontname=monospace];\n");
+        dot.push_str("    exit [shape=doublecircle];\n");
+
+        let needs_node = |wanted: Target| {
+            self.blocks
+                .iter()
+                .any(|block| block.successors.contains(&wanted))
+        };
+
+        if needs_node(Target::Invalid) {
+            dot.push_str("    invalid [shape=octagon];\n");
+        }
+
+        if needs_node(Target::Dynamic) {
+            dot.push_str("    dynamic [shape=diamond, label=\"?\"];\n");
+        }
+
+        for block in &self.blocks {
+            let mut label = String::new();
+            for index in block.start..block.end {
+                let _ = write!(label, "{index}: {}\\l", self.program[index]);
+            }
+
+            let header = if self.loop_headers.contains(&block.start) {
+                ", style=bold"
+            } else {
+                ""
+            };
+
+            let _ = writeln!(dot, "    b{} [label=\"{label}\"{header}];", block.start);
+
+            for (i, successor) in block.successors.iter().enumerate() {
+                let node = match successor {
+                    Target::Instruction(index) => format!("b{index}"),
+                    other => format!("{other:?}").to_lowercase(),
+                };
+
+                // the fallthrough edge of a conditional jump is drawn dashed.
+                let style = if i > 0 { " [style=dashed]" } else { "" };
+                let _ = writeln!(dot, "    b{} -> {node}{style};", block.start);
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// An annotated listing: block boundaries, loop headers, resolved jump targets and `tgl` reach.
+impl Display for Disassembly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for block in &self.blocks {
+            let successors = block
+                .successors
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let header = if self.loop_headers.contains(&block.start) {
+                ", loop header"
+            } else {
+                ""
+            };
+
+            writeln!(
+                f,
+                "; block {}..{}{header} -> {successors}",
+                block.start, block.end
+            )?;
+
+            for index in block.start..block.end {
+                let mut notes = vec![];
+
+                if let Some(target) = self.jump_target(index) {
+                    notes.push(format!("jumps to {target}"));
+                }
+
+                if let Some(target) = self.toggle_target(index) {
+                    notes.push(format!("toggles {target}"));
+                }
+
+                let toggled_by = self.toggled_by(index).collect::<Vec<_>>();
+                if !toggled_by.is_empty() {
+                    let sources = toggled_by
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>();
+                    notes.push(format!("may be toggled by {}", sources.join(", ")));
+                }
+
+                let instruction = self.program[index].to_string();
+                if notes.is_empty() {
+                    writeln!(f, "{index:>4}  {instruction}")?;
+                } else {
+                    writeln!(f, "{index:>4}  {instruction:<12} ; {}", notes.join("; "))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl PrototypeComputer {
+    /// Parses a program for this computer's registers and analyzes its control flow.
+    pub fn disassemble(&self, program: &[&str]) -> Result<Disassembly, ParseError> {
+        Ok(Disassembly::new(&self.parse_program(program)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Effect, Halt, Instruction, Machine};
+
+    const PROGRAM: [&str; 7] = [
+        "cpy 2 a", "tgl a", "dec a", "jnz a -2", "jnz c 2", "inc b", "jnz 1 3",
+    ];
+
+    #[test]
+    fn splits_basic_blocks() {
+        let disassembly = PrototypeComputer::new().disassemble(&PROGRAM).unwrap();
+
+        let blocks = disassembly
+            .blocks()
+            .iter()
+            .map(|block| (block.start, block.end, block.successors.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            blocks,
+            vec![
+                (0, 1, vec![Target::Instruction(1)]),
+                (1, 4, vec![Target::Instruction(1), Target::Instruction(4)]),
+                (4, 5, vec![Target::Instruction(6), Target::Instruction(5)]),
+                (5, 6, vec![Target::Instruction(6)]),
+                (6, 7, vec![Target::Exit]),
+            ]
+        );
+        assert_eq!(disassembly.loop_headers().collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn annotates_toggle_reach() {
+        let disassembly = PrototypeComputer::new().disassemble(&PROGRAM).unwrap();
+
+        assert_eq!(disassembly.toggle_target(1), Some(Target::Dynamic));
+        assert_eq!(disassembly.toggled_by(5).collect::<Vec<_>>(), vec![1]);
+
+        let listing = disassembly.to_string();
+        assert!(listing.contains("; block 1..4, loop header -> 1, 4"));
+        assert!(listing.contains("   3  jnz a -2     ; jumps to 1; may be toggled by 1"));
+    }
+
+    /// `jgz x y` from 2017, jumping by `y` if `x` is greater than zero.
+    #[derive(Debug)]
+    struct Jgz;
+
+    impl Instruction for Jgz {
+        fn name(&self) -> &'static str {
+            "jgz"
+        }
+
+        fn arity(&self) -> usize {
+            2
+        }
+
+        fn may_jump(&self) -> bool {
+            true
+        }
+
+        fn execute(
+            &self,
+            machine: &mut Machine<'_>,
+            x: Operand,
+            y: Option<Operand>,
+        ) -> Result<Effect, Halt> {
+            match y {
+                Some(skip) if machine.value(x) > 0 => Ok(Effect::Jump(machine.value(skip))),
+                _ => Ok(Effect::Next),
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_fallthrough_of_custom_jumps() {
+        let mut computer = PrototypeComputer::new();
+        computer.register_instruction(&Jgz);
+
+        // `jgz -1 2` never jumps, although its condition is an immediate.
+        let disassembly = computer.disassemble(&["jgz -1 2", "inc a"]).unwrap();
+
+        assert_eq!(
+            disassembly.blocks()[0].successors,
+            vec![Target::Dynamic, Target::Instruction(1)]
+        );
+    }
+
+    #[test]
+    fn exports_dot() {
+        let dot = PrototypeComputer::new()
+            .disassemble(&["jnz a 2", "inc a", "jnz b -2"])
+            .unwrap()
+            .to_dot();
+
+        assert!(dot.starts_with("digraph assembunny {"));
+        assert!(dot.contains("    b0 [label=\"0: jnz a 2\\l\", style=bold];"));
+        assert!(dot.contains("    b0 -> b2;\n    b0 -> b1 [style=dashed];"));
+        assert!(dot.contains("    b2 -> b0;\n    b2 -> exit [style=dashed];"));
+    }
+}
@@ -0,0 +1,57 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use super::{Halt, ParseError, PrototypeComputerInstruction};
+
+/// An arithmetic result did not fit into the register width while running in checked mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowError {
+    /// Index of the instruction (or fused block) that overflowed.
+    pub index: usize,
+    pub instruction: PrototypeComputerInstruction,
+}
+
+impl Error for OverflowError {}
+
+impl Display for OverflowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "arithmetic overflow at instruction {} (`{}`)",
+            self.index, self.instruction
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrototypeComputerError {
+    Parse(ParseError),
+    UnknownRegister(char),
+    OutOfRange {
+        register: char,
+        value: i64,
+    },
+    /// The program stopped with a [fault](Halt::is_fault).
+    Fault(Halt),
+}
+
+impl Error for PrototypeComputerError {}
+
+impl Display for PrototypeComputerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrototypeComputerError::Parse(e) => write!(f, "{e}"),
+            PrototypeComputerError::UnknownRegister(r) => write!(f, "unknown register `{r}`"),
+            PrototypeComputerError::OutOfRange { register, value } => {
+                write!(f, "{value} does not fit into register `{register}`")
+            }
+            PrototypeComputerError::Fault(halt) => write!(f, "{halt}"),
+        }
+    }
+}
+
+impl From<ParseError> for PrototypeComputerError {
+    fn from(e: ParseError) -> Self {
+        PrototypeComputerError::Parse(e)
+    }
+}
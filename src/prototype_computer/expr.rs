@@ -0,0 +1,243 @@
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+/// A product of symbols with their exponents, sorted by symbol. Empty for the constant term.
+type Monomial = Vec<(char, u32)>;
+
+/// A polynomial with integer coefficients over symbolic register values.
+///
+/// Expressions are kept in canonical form, so structurally equal polynomials compare equal.
+/// Arithmetic is checked and returns `None` if a coefficient or exponent overflows.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Expr {
+    // coefficients are never zero.
+    terms: BTreeMap<Monomial, i128>,
+}
+
+impl Expr {
+    pub fn constant(val: i128) -> Self {
+        let mut terms = BTreeMap::new();
+        if val != 0 {
+            terms.insert(vec![], val);
+        }
+        Self { terms }
+    }
+
+    pub fn symbol(symbol: char) -> Self {
+        Self {
+            terms: BTreeMap::from([(vec![(symbol, 1)], 1)]),
+        }
+    }
+
+    fn add_term(&mut self, monomial: Monomial, coefficient: i128) -> Option<()> {
+        match self.terms.entry(monomial) {
+            Entry::Occupied(mut entry) => {
+                let sum = entry.get().checked_add(coefficient)?;
+                if sum == 0 {
+                    entry.remove();
+                } else {
+                    *entry.get_mut() = sum;
+                }
+            }
+            Entry::Vacant(entry) => {
+                if coefficient != 0 {
+                    entry.insert(coefficient);
+                }
+            }
+        }
+
+        Some(())
+    }
+
+    /// The value of an expression without symbols.
+    pub fn as_constant(&self) -> Option<i128> {
+        match self.terms.iter().next() {
+            None => Some(0),
+            Some((monomial, &c)) if monomial.is_empty() && self.terms.len() == 1 => Some(c),
+            Some(_) => None,
+        }
+    }
+
+    /// Symbols the expression depends on, in order.
+    pub fn symbols(&self) -> impl Iterator<Item = char> + '_ {
+        let mut symbols = self
+            .terms
+            .keys()
+            .flatten()
+            .map(|&(s, _)| s)
+            .collect::<Vec<_>>();
+        symbols.sort_unstable();
+        symbols.dedup();
+        symbols.into_iter()
+    }
+
+    /// Splits `k * s + m` into `(s, k, m)`, if the expression has that shape with `k != 0`.
+    pub fn as_linear(&self) -> Option<(char, i128, i128)> {
+        let mut linear = None;
+        let mut constant = 0;
+
+        for (monomial, &c) in &self.terms {
+            match monomial.as_slice() {
+                [] => constant = c,
+                [(s, 1)] if linear.is_none() => linear = Some((*s, c)),
+                _ => return None,
+            }
+        }
+
+        linear.map(|(s, k)| (s, k, constant))
+    }
+
+    /// Replaces a symbol by a value.
+    pub fn substitute(&self, symbol: char, val: i128) -> Option<Self> {
+        let mut result = Self::default();
+
+        for (monomial, &c) in &self.terms {
+            let mut coefficient = c;
+            let mut rest = vec![];
+
+            for &(s, exp) in monomial {
+                if s == symbol {
+                    coefficient = coefficient.checked_mul(val.checked_pow(exp)?)?;
+                } else {
+                    rest.push((s, exp));
+                }
+            }
+
+            result.add_term(rest, coefficient)?;
+        }
+
+        Some(result)
+    }
+
+    /// Evaluates the expression, or returns `None` if a symbol has no value or a result
+    /// overflows.
+    pub fn evaluate(&self, value_of: impl Fn(char) -> Option<i128>) -> Option<i128> {
+        self.terms.iter().try_fold(0i128, |sum, (monomial, &c)| {
+            let term = monomial.iter().try_fold(c, |product, &(s, exp)| {
+                product.checked_mul(value_of(s)?.checked_pow(exp)?)
+            })?;
+            sum.checked_add(term)
+        })
+    }
+
+    pub fn checked_add(mut self, rhs: &Expr) -> Option<Self> {
+        for (monomial, &c) in &rhs.terms {
+            self.add_term(monomial.clone(), c)?;
+        }
+        Some(self)
+    }
+
+    pub fn checked_neg(mut self) -> Option<Self> {
+        for c in self.terms.values_mut() {
+            *c = c.checked_neg()?;
+        }
+        Some(self)
+    }
+
+    pub fn checked_sub(self, rhs: &Expr) -> Option<Self> {
+        self.checked_add(&rhs.clone().checked_neg()?)
+    }
+
+    pub fn checked_mul(&self, rhs: &Expr) -> Option<Self> {
+        let mut result = Self::default();
+
+        for (left, &a) in &self.terms {
+            for (right, &b) in &rhs.terms {
+                let mut exponents = BTreeMap::new();
+                for &(s, exp) in left.iter().chain(right) {
+                    let total: &mut u32 = exponents.entry(s).or_insert(0);
+                    *total = total.checked_add(exp)?;
+                }
+
+                result.add_term(exponents.into_iter().collect(), a.checked_mul(b)?)?;
+            }
+        }
+
+        Some(result)
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+
+        // highest degree first, so `a^2 + 2*a + 1` reads as usual.
+        let mut terms = self.terms.iter().collect::<Vec<_>>();
+        terms.sort_by_key(|(monomial, _)| {
+            std::cmp::Reverse(monomial.iter().map(|&(_, exp)| exp).sum::<u32>())
+        });
+
+        for (i, (monomial, &c)) in terms.into_iter().enumerate() {
+            let sign = if c < 0 { "-" } else { "+" };
+            let magnitude = c.unsigned_abs();
+
+            match (i, sign) {
+                (0, "-") => write!(f, "-")?,
+                (0, _) => {}
+                _ => write!(f, " {sign} ")?,
+            }
+
+            let factors = monomial
+                .iter()
+                .map(|&(s, exp)| match exp {
+                    1 => s.to_string(),
+                    _ => format!("{s}^{exp}"),
+                })
+                .collect::<Vec<_>>()
+                .join("*");
+
+            match (magnitude, factors.is_empty()) {
+                (_, true) => write!(f, "{magnitude}")?,
+                (1, false) => write!(f, "{factors}")?,
+                (_, false) => write!(f, "{magnitude}*{factors}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_canonical_form() {
+        let a = Expr::symbol('a');
+        let successor = a.clone().checked_add(&Expr::constant(1)).unwrap();
+        let square = successor.checked_mul(&successor).unwrap();
+
+        assert_eq!(square.to_string(), "a^2 + 2*a + 1");
+        assert_eq!(square.clone().checked_sub(&square), Some(Expr::constant(0)));
+        assert_eq!(
+            a.clone()
+                .checked_sub(&Expr::constant(3))
+                .and_then(|expr| expr.as_linear()),
+            Some(('a', 1, -3))
+        );
+        assert_eq!(square.as_linear(), None);
+        assert_eq!(
+            square
+                .substitute('a', 4)
+                .and_then(|expr| expr.as_constant()),
+            Some(25)
+        );
+        assert_eq!(square.evaluate(|_| Some(-1)), Some(0));
+        assert_eq!(a.checked_neg().unwrap().to_string(), "-a");
+    }
+
+    #[test]
+    fn reports_overflowing_coefficients() {
+        let a = Expr::symbol('a');
+        let huge = a.checked_mul(&Expr::constant(i128::MAX)).unwrap();
+
+        assert_eq!(huge.clone().checked_add(&huge), None);
+        assert_eq!(huge.checked_mul(&Expr::constant(2)), None);
+        assert_eq!(huge.substitute('a', 2), None);
+        assert_eq!(Expr::constant(i128::MIN).checked_neg(), None);
+        assert_eq!(Expr::constant(i128::MIN).to_string(), i128::MIN.to_string());
+    }
+}
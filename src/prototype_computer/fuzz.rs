@@ -0,0 +1,233 @@
+//! Differential testing: random programs must behave the same on every backend as on the
+//! reference interpreter. A disagreement is shrunk to a minimal program before it is reported.
+
+use super::{
+    to_source, Backend, Budget, Halt, Opcode, Operand, Outcome, PrototypeComputer,
+    PrototypeComputerInstruction, RegisterFile,
+};
+
+const REGISTERS: [char; 4] = ['a', 'b', 'c', 'd'];
+
+/// Steps per run. Small, so that random infinite loops end quickly.
+const MAX_STEPS: u64 = 2_000;
+
+/// A xorshift generator, so that failures reproduce from the seed without extra dependencies.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())]
+    }
+}
+
+fn random_operand(rng: &mut Rng) -> Operand {
+    match rng.below(8) {
+        0..=3 => Operand::Register(rng.pick(&REGISTERS)),
+        // large enough to make fused loops overflow.
+        4 => Operand::Immediate(rng.pick(&[i64::from(i32::MAX), i64::from(i32::MIN), 1 << 20])),
+        _ => Operand::Immediate(rng.pick(&[-3, -2, -1, 0, 1, 2, 3])),
+    }
+}
+
+/// A program the parser accepts, biased towards the loop idioms the optimizer fuses.
+fn random_program(rng: &mut Rng) -> Vec<PrototypeComputerInstruction> {
+    let len = 1 + rng.below(12);
+
+    (0..len)
+        .map(|_| {
+            let opcode = rng.pick(&Opcode::BUILTIN);
+            let x = random_operand(rng);
+            let y = (opcode.arity() == 2).then(|| match opcode {
+                Opcode::Cpy => Operand::Register(rng.pick(&REGISTERS)),
+                _ => random_operand(rng),
+            });
+            PrototypeComputerInstruction::new(opcode, x, y)
+        })
+        .collect()
+}
+
+/// Everything observable about a bounded run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Run {
+    outputs: Vec<i64>,
+    halt: Halt,
+    registers: RegisterFile,
+    steps: u64,
+}
+
+fn run(program: &[PrototypeComputerInstruction], backend: Backend, optimizations: bool) -> Run {
+    let mut computer = PrototypeComputer::new();
+    computer.set_backend(backend);
+    computer.set_optimizations(optimizations);
+    computer.load_instructions(program.to_vec()).unwrap();
+
+    let mut outputs = computer.outputs(Budget::steps(MAX_STEPS));
+    let values = outputs.by_ref().collect();
+    let halt = outputs.halt().unwrap();
+
+    Run {
+        outputs: values,
+        halt,
+        registers: computer.registers().clone(),
+        steps: computer.steps(),
+    }
+}
+
+/// Describes how the backends disagree on a program, if they do.
+fn disagreement(program: &[PrototypeComputerInstruction]) -> Option<String> {
+    for optimizations in [false, true] {
+        let reference = run(program, Backend::Interpreter, optimizations);
+        let compiled = run(program, Backend::Compiled, optimizations);
+
+        if compiled != reference {
+            return Some(format!(
+                "optimizations: {optimizations}\ninterpreter: {reference:?}\ncompiled: {compiled:?}"
+            ));
+        }
+    }
+
+    // fused loops take fewer steps, so only finished runs are comparable.
+    let plain = run(program, Backend::Interpreter, false);
+    if plain.halt == Halt::BudgetExhausted {
+        return None;
+    }
+
+    let fused = run(program, Backend::Interpreter, true);
+    if (&fused.outputs, fused.halt, &fused.registers)
+        != (&plain.outputs, plain.halt, &plain.registers)
+    {
+        return Some(format!("plain: {plain:?}\nfused: {fused:?}"));
+    }
+
+    let mut computer = PrototypeComputer::new();
+    computer.load_instructions(program.to_vec()).unwrap();
+    let closed = computer.closed_form(&[]);
+
+    let symbolic = match &closed.cases()[0].outcome {
+        Outcome::Exited { outputs, .. } => {
+            let outputs = outputs
+                .iter()
+                .map(|expr| expr.as_constant().and_then(|val| i64::try_from(val).ok()))
+                .collect::<Option<Vec<_>>>();
+            let registers = closed.evaluate(&[]);
+            (outputs, Halt::Exited, registers)
+        }
+        Outcome::Fault(halt) => (None, *halt, None),
+        Outcome::Unresolved(reason) => return Some(format!("symbolic: unresolved ({reason:?})")),
+    };
+
+    let expected = match plain.halt {
+        Halt::Exited => (Some(plain.outputs), Halt::Exited, Some(plain.registers)),
+        halt => (None, halt, None),
+    };
+
+    (symbolic != expected).then(|| format!("plain: {expected:?}\nsymbolic: {symbolic:?}"))
+}
+
+/// Simpler variants of a program: one instruction removed, or one operand made smaller.
+fn simplifications(
+    program: &[PrototypeComputerInstruction],
+) -> Vec<Vec<PrototypeComputerInstruction>> {
+    let mut candidates = vec![];
+
+    for i in 0..program.len() {
+        let mut removed = program.to_vec();
+        removed.remove(i);
+        candidates.push(removed);
+    }
+
+    for (i, instruction) in program.iter().enumerate() {
+        let operands = [Some(instruction.x), instruction.y];
+
+        for (position, operand) in operands.into_iter().enumerate() {
+            let simpler = match operand {
+                Some(Operand::Immediate(0)) | None => vec![],
+                Some(Operand::Immediate(val)) => vec![0, val / 2, val - val.signum()],
+                Some(Operand::Register(_)) => vec![0, 1],
+            };
+
+            for val in simpler {
+                // registers stay registers where the opcode writes to them.
+                if position == 1 && instruction.opcode == Opcode::Cpy {
+                    continue;
+                }
+
+                let mut changed = program.to_vec();
+                let operand = Operand::Immediate(val);
+                match position {
+                    0 => changed[i].x = operand,
+                    _ => changed[i].y = Some(operand),
+                }
+                candidates.push(changed);
+            }
+        }
+    }
+
+    candidates.retain(|candidate| candidate != program);
+    candidates
+}
+
+/// Greedily simplifies a program for as long as it keeps failing.
+fn shrink(
+    mut program: Vec<PrototypeComputerInstruction>,
+    fails: impl Fn(&[PrototypeComputerInstruction]) -> bool,
+) -> Vec<PrototypeComputerInstruction> {
+    while let Some(simpler) = simplifications(&program)
+        .into_iter()
+        .find(|candidate| fails(candidate))
+    {
+        program = simpler;
+    }
+
+    program
+}
+
+#[test]
+fn backends_agree_on_random_programs() {
+    let mut rng = Rng(0x2016_1223);
+
+    for _ in 0..2_000 {
+        let program = random_program(&mut rng);
+
+        if disagreement(&program).is_some() {
+            let minimal = shrink(program, |program| disagreement(program).is_some());
+            panic!(
+                "backends disagree on\n{}{}",
+                to_source(&minimal),
+                disagreement(&minimal).unwrap()
+            );
+        }
+    }
+}
+
+#[test]
+fn shrinks_to_minimal_programs() {
+    let sends_non_zero = |program: &[PrototypeComputerInstruction]| {
+        run(program, Backend::Interpreter, false)
+            .outputs
+            .iter()
+            .any(|&val| val != 0)
+    };
+
+    let mut rng = Rng(25);
+    let program = std::iter::repeat_with(|| random_program(&mut rng))
+        .find(|program| program.len() > 4 && sends_non_zero(program))
+        .unwrap();
+
+    let minimal = shrink(program, sends_non_zero);
+
+    assert_eq!(minimal.len(), 1);
+    assert_eq!(minimal[0].opcode, Opcode::Out);
+    assert!(matches!(minimal[0].x, Operand::Immediate(1 | -1)));
+}
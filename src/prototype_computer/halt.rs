@@ -0,0 +1,117 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use super::OverflowError;
+
+/// How often the wall-clock deadline is checked, in steps.
+const DEADLINE_CHECK_INTERVAL: u64 = 1024;
+
+/// Why [`PrototypeComputer::run`](super::PrototypeComputer::run) returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Halt {
+    /// The instruction pointer left the end of the program.
+    Exited,
+    /// An `out` instruction sent a value. Running again resumes after it.
+    Output(i64),
+    /// The step budget or deadline ran out. Running again resumes where it stopped.
+    BudgetExhausted,
+    /// A `jnz` tried to jump before the start of the program.
+    InvalidJump {
+        index: usize,
+        offset: i64,
+    },
+    /// The instruction's operands do not match its opcode, e.g. a `cpy` without destination.
+    InvalidInstruction {
+        index: usize,
+    },
+    Overflow(OverflowError),
+}
+
+impl Halt {
+    /// Whether the machine stopped because something went wrong.
+    pub fn is_fault(&self) -> bool {
+        matches!(
+            self,
+            Halt::InvalidJump { .. } | Halt::InvalidInstruction { .. } | Halt::Overflow(_)
+        )
+    }
+}
+
+impl From<OverflowError> for Halt {
+    fn from(e: OverflowError) -> Self {
+        Halt::Overflow(e)
+    }
+}
+
+impl Display for Halt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Halt::Exited => write!(f, "program exited"),
+            Halt::Output(val) => write!(f, "program sent {val}"),
+            Halt::BudgetExhausted => write!(f, "budget exhausted"),
+            Halt::InvalidJump { index, offset } => {
+                write!(f, "invalid jump by {offset} at instruction {index}")
+            }
+            Halt::InvalidInstruction { index } => write!(f, "invalid instruction at {index}"),
+            Halt::Overflow(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Limits how long a run may take. The default budget is unlimited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Budget {
+    pub max_steps: Option<u64>,
+    pub deadline: Option<Instant>,
+}
+
+impl Budget {
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Executes at most `max_steps` instructions. A fused optimizer block counts as one step.
+    pub fn steps(max_steps: u64) -> Self {
+        Self {
+            max_steps: Some(max_steps),
+            deadline: None,
+        }
+    }
+
+    pub fn deadline(deadline: Instant) -> Self {
+        Self {
+            max_steps: None,
+            deadline: Some(deadline),
+        }
+    }
+
+    pub fn timeout(timeout: Duration) -> Self {
+        Self::deadline(Instant::now() + timeout)
+    }
+}
+
+/// A [`Budget`] pinned to the step counter of a running computer.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Limit {
+    stop_at: Option<u64>,
+    deadline: Option<Instant>,
+}
+
+impl Limit {
+    pub(super) fn new(budget: Budget, steps: u64) -> Self {
+        Self {
+            stop_at: budget.max_steps.map(|max| steps.saturating_add(max)),
+            deadline: budget.deadline,
+        }
+    }
+
+    pub(super) fn is_exhausted(&self, steps: u64) -> bool {
+        if self.stop_at.is_some_and(|stop_at| steps >= stop_at) {
+            return true;
+        }
+
+        self.deadline.is_some_and(|deadline| {
+            steps.is_multiple_of(DEADLINE_CHECK_INTERVAL) && Instant::now() >= deadline
+        })
+    }
+}
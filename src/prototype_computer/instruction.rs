@@ -0,0 +1,167 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+
+use super::Instruction;
+
+/// An assembunny operation. Operations from later puzzles plug in as [`Opcode::Custom`].
+#[derive(Debug, Clone, Copy)]
+pub enum Opcode {
+    Cpy,
+    Inc,
+    Dec,
+    Jnz,
+    Tgl,
+    Out,
+    Custom(&'static dyn Instruction),
+}
+
+impl Opcode {
+    /// Looks up a built-in opcode by its mnemonic, e.g. `"cpy"`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::BUILTIN
+            .into_iter()
+            .find(|opcode| opcode.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        self.definition().name()
+    }
+
+    /// Number of operands the opcode takes.
+    pub fn arity(self) -> usize {
+        self.definition().arity()
+    }
+
+    /// The opcode an instruction turns into when hit by `tgl`.
+    pub fn toggled(self) -> Self {
+        self.definition().toggled()
+    }
+
+    /// Built-in opcodes first, then custom ones by name.
+    fn key(self) -> (usize, &'static str) {
+        let rank = match self {
+            Opcode::Cpy => 0,
+            Opcode::Inc => 1,
+            Opcode::Dec => 2,
+            Opcode::Jnz => 3,
+            Opcode::Tgl => 4,
+            Opcode::Out => 5,
+            Opcode::Custom(_) => 6,
+        };
+
+        (rank, self.name())
+    }
+}
+
+impl PartialEq for Opcode {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Opcode {}
+
+impl PartialOrd for Opcode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Opcode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Hash for Opcode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl Display for Opcode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// An instruction argument: either a register name or an immediate value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operand {
+    Register(char),
+    Immediate(i64),
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operand::Register(r) => write!(f, "{r}"),
+            Operand::Immediate(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+/// A decoded instruction. Single-operand opcodes leave `y` empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PrototypeComputerInstruction {
+    pub opcode: Opcode,
+    pub x: Operand,
+    pub y: Option<Operand>,
+}
+
+impl PrototypeComputerInstruction {
+    pub fn new(opcode: Opcode, x: Operand, y: Option<Operand>) -> Self {
+        Self { opcode, x, y }
+    }
+}
+
+impl Display for PrototypeComputerInstruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.y {
+            Some(y) => write!(f, "{} {} {}", self.opcode, self.x, y),
+            None => write!(f, "{} {}", self.opcode, self.x),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Why a line of assembunny could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorReason {
+    UnknownOpcode(String),
+    WrongArity { expected: usize, found: usize },
+    InvalidOperand(String),
+}
+
+impl Display for ParseErrorReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorReason::UnknownOpcode(name) => write!(f, "unknown opcode `{name}`"),
+            ParseErrorReason::WrongArity { expected, found } => {
+                write!(f, "expected {expected} operand(s), found {found}")
+            }
+            ParseErrorReason::InvalidOperand(operand) => {
+                write!(f, "`{operand}` is neither a register nor an integer")
+            }
+        }
+    }
+}
+
+/// An error which can be returned when parsing an assembunny program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number in the source program.
+    pub line: usize,
+    pub text: String,
+    pub reason: ParseErrorReason,
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {} (`{}`): {}", self.line, self.text, self.reason)
+    }
+}
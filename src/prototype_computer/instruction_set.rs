@@ -0,0 +1,398 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+
+use super::{
+    Arithmetic, Halt, Opcode, Operand, OverflowError, PrototypeComputerInstruction, RegisterFile,
+};
+
+/// What executing an instruction asks the computer to do next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// Continue with the following instruction.
+    Next,
+    /// Continue at an offset relative to this instruction.
+    Jump(i64),
+    /// Send a value, then continue with the following instruction.
+    Output(i64),
+    /// Toggle the instruction at an offset relative to this one, then continue with the
+    /// following instruction.
+    Toggle(i64),
+}
+
+/// The part of a [`PrototypeComputer`](super::PrototypeComputer) an instruction may act on.
+#[derive(Debug)]
+pub struct Machine<'a> {
+    pub(super) registers: &'a mut RegisterFile,
+    pub(super) arithmetic: Arithmetic,
+    pub(super) input: &'a mut VecDeque<i64>,
+    pub(super) index: usize,
+    pub(super) instruction: PrototypeComputerInstruction,
+}
+
+impl Machine<'_> {
+    pub fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(r) => self.registers.read(r),
+            Operand::Immediate(v) => v,
+        }
+    }
+
+    /// Stores an exact result into a register, failing if it overflows in checked mode.
+    pub fn store(&mut self, register: char, val: i128) -> Result<(), Halt> {
+        let fitted = self.arithmetic.fit(val).ok_or(OverflowError {
+            index: self.index,
+            instruction: self.instruction,
+        })?;
+
+        self.registers.write(register, fitted);
+
+        Ok(())
+    }
+
+    /// Takes the oldest queued input value.
+    pub fn read_input(&mut self) -> Option<i64> {
+        self.input.pop_front()
+    }
+}
+
+/// The definition of an assembunny operation: how it is spelled, what it does and what `tgl`
+/// turns it into.
+///
+/// Implement this for an instruction from a later puzzle and register it with
+/// [`PrototypeComputer::register_instruction`](super::PrototypeComputer::register_instruction).
+/// The optimizer and the compiled backend leave such instructions to the interpreter, and the
+/// static analyses treat them conservatively based on [`may_jump`](Self::may_jump) and
+/// [`may_toggle`](Self::may_toggle).
+pub trait Instruction: Debug + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Number of operands, either 1 or 2.
+    fn arity(&self) -> usize;
+
+    /// The opcode this instruction turns into when hit by `tgl`. By default, one-operand
+    /// instructions become `inc` and two-operand instructions become `jnz`.
+    fn toggled(&self) -> Opcode {
+        match self.arity() {
+            1 => Opcode::Inc,
+            _ => Opcode::Jnz,
+        }
+    }
+
+    /// Whether [`execute`](Self::execute) may return [`Effect::Jump`].
+    fn may_jump(&self) -> bool {
+        false
+    }
+
+    /// Whether [`execute`](Self::execute) may return [`Effect::Toggle`].
+    fn may_toggle(&self) -> bool {
+        false
+    }
+
+    /// Executes the instruction. `y` is present exactly for two-operand instructions. An
+    /// instruction only writes to registers named by its operands.
+    fn execute(
+        &self,
+        machine: &mut Machine<'_>,
+        x: Operand,
+        y: Option<Operand>,
+    ) -> Result<Effect, Halt>;
+}
+
+#[derive(Debug)]
+struct Cpy;
+
+impl Instruction for Cpy {
+    fn name(&self) -> &'static str {
+        "cpy"
+    }
+
+    fn arity(&self) -> usize {
+        2
+    }
+
+    fn execute(
+        &self,
+        machine: &mut Machine<'_>,
+        x: Operand,
+        y: Option<Operand>,
+    ) -> Result<Effect, Halt> {
+        if let Some(Operand::Register(dest)) = y {
+            machine.store(dest, i128::from(machine.value(x)))?;
+        }
+
+        Ok(Effect::Next)
+    }
+}
+
+#[derive(Debug)]
+struct Inc;
+
+impl Instruction for Inc {
+    fn name(&self) -> &'static str {
+        "inc"
+    }
+
+    fn arity(&self) -> usize {
+        1
+    }
+
+    fn toggled(&self) -> Opcode {
+        Opcode::Dec
+    }
+
+    fn execute(
+        &self,
+        machine: &mut Machine<'_>,
+        x: Operand,
+        _: Option<Operand>,
+    ) -> Result<Effect, Halt> {
+        if let Operand::Register(target) = x {
+            machine.store(target, i128::from(machine.value(x)) + 1)?;
+        }
+
+        Ok(Effect::Next)
+    }
+}
+
+#[derive(Debug)]
+struct Dec;
+
+impl Instruction for Dec {
+    fn name(&self) -> &'static str {
+        "dec"
+    }
+
+    fn arity(&self) -> usize {
+        1
+    }
+
+    fn execute(
+        &self,
+        machine: &mut Machine<'_>,
+        x: Operand,
+        _: Option<Operand>,
+    ) -> Result<Effect, Halt> {
+        if let Operand::Register(target) = x {
+            machine.store(target, i128::from(machine.value(x)) - 1)?;
+        }
+
+        Ok(Effect::Next)
+    }
+}
+
+#[derive(Debug)]
+struct Jnz;
+
+impl Instruction for Jnz {
+    fn name(&self) -> &'static str {
+        "jnz"
+    }
+
+    fn arity(&self) -> usize {
+        2
+    }
+
+    fn toggled(&self) -> Opcode {
+        Opcode::Cpy
+    }
+
+    fn may_jump(&self) -> bool {
+        true
+    }
+
+    fn execute(
+        &self,
+        machine: &mut Machine<'_>,
+        x: Operand,
+        y: Option<Operand>,
+    ) -> Result<Effect, Halt> {
+        match y {
+            Some(skip) if machine.value(x) != 0 => Ok(Effect::Jump(machine.value(skip))),
+            _ => Ok(Effect::Next),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Tgl;
+
+impl Instruction for Tgl {
+    fn name(&self) -> &'static str {
+        "tgl"
+    }
+
+    fn arity(&self) -> usize {
+        1
+    }
+
+    fn may_toggle(&self) -> bool {
+        true
+    }
+
+    fn execute(
+        &self,
+        machine: &mut Machine<'_>,
+        x: Operand,
+        _: Option<Operand>,
+    ) -> Result<Effect, Halt> {
+        Ok(Effect::Toggle(machine.value(x)))
+    }
+}
+
+#[derive(Debug)]
+struct Out;
+
+impl Instruction for Out {
+    fn name(&self) -> &'static str {
+        "out"
+    }
+
+    fn arity(&self) -> usize {
+        1
+    }
+
+    fn execute(
+        &self,
+        machine: &mut Machine<'_>,
+        x: Operand,
+        _: Option<Operand>,
+    ) -> Result<Effect, Halt> {
+        Ok(Effect::Output(machine.value(x)))
+    }
+}
+
+impl Opcode {
+    /// The built-in opcodes, in the order they are looked up by name.
+    pub const BUILTIN: [Opcode; 6] = [
+        Opcode::Cpy,
+        Opcode::Inc,
+        Opcode::Dec,
+        Opcode::Jnz,
+        Opcode::Tgl,
+        Opcode::Out,
+    ];
+
+    /// The definition the interpreter dispatches to.
+    pub fn definition(self) -> &'static dyn Instruction {
+        match self {
+            Opcode::Cpy => &Cpy,
+            Opcode::Inc => &Inc,
+            Opcode::Dec => &Dec,
+            Opcode::Jnz => &Jnz,
+            Opcode::Tgl => &Tgl,
+            Opcode::Out => &Out,
+            Opcode::Custom(definition) => definition,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Backend, Budget, ParseErrorReason, PrototypeComputer};
+
+    /// `mul x y` from 2017, storing `x * y` in `y`.
+    #[derive(Debug)]
+    struct Mul;
+
+    impl Instruction for Mul {
+        fn name(&self) -> &'static str {
+            "mul"
+        }
+
+        fn arity(&self) -> usize {
+            2
+        }
+
+        fn execute(
+            &self,
+            machine: &mut Machine<'_>,
+            x: Operand,
+            y: Option<Operand>,
+        ) -> Result<Effect, Halt> {
+            if let Some(Operand::Register(dest)) = y {
+                let product = i128::from(machine.value(x))
+                    * i128::from(machine.value(Operand::Register(dest)));
+                machine.store(dest, product)?;
+            }
+
+            Ok(Effect::Next)
+        }
+    }
+
+    /// `in x`, reading the next queued input into `x`.
+    #[derive(Debug)]
+    struct In;
+
+    impl Instruction for In {
+        fn name(&self) -> &'static str {
+            "in"
+        }
+
+        fn arity(&self) -> usize {
+            1
+        }
+
+        fn execute(
+            &self,
+            machine: &mut Machine<'_>,
+            x: Operand,
+            _: Option<Operand>,
+        ) -> Result<Effect, Halt> {
+            if let (Operand::Register(dest), Some(val)) = (x, machine.read_input()) {
+                machine.store(dest, i128::from(val))?;
+            }
+
+            Ok(Effect::Next)
+        }
+    }
+
+    #[test]
+    fn runs_registered_instructions() {
+        let program = ["in a", "mul 7 a", "out a"];
+
+        let mut computer = PrototypeComputer::new();
+        assert_eq!(
+            computer.load_program(&program).unwrap_err().reason,
+            ParseErrorReason::UnknownOpcode("in".into())
+        );
+
+        for backend in [Backend::Interpreter, Backend::Compiled] {
+            let mut computer = PrototypeComputer::new();
+            computer.set_backend(backend);
+            computer.register_instruction(&Mul);
+            computer.register_instruction(&In);
+            computer.load_program(&program).unwrap();
+            computer.push_input(6);
+
+            assert_eq!(computer.run(Budget::unlimited()), Halt::Output(42));
+        }
+    }
+
+    #[test]
+    fn toggles_by_definition() {
+        let toggles = Opcode::BUILTIN.map(|opcode| (opcode.name(), opcode.toggled().name()));
+        assert_eq!(
+            toggles,
+            [
+                ("cpy", "jnz"),
+                ("inc", "dec"),
+                ("dec", "inc"),
+                ("jnz", "cpy"),
+                ("tgl", "inc"),
+                ("out", "inc"),
+            ]
+        );
+
+        // the toggled `mul 7 a` becomes `jnz 7 a`, which jumps past the end.
+        let mut computer = PrototypeComputer::new();
+        computer.register_instruction(&Mul);
+        computer
+            .load_program(&["cpy 2 b", "tgl b", "cpy 6 a", "mul 7 a"])
+            .unwrap();
+
+        assert_eq!(computer.run(Budget::unlimited()), Halt::Exited);
+        assert_eq!(computer.get_register('a'), Some(6));
+        assert_eq!(computer.program()[3].opcode, Opcode::Jnz);
+    }
+}
@@ -0,0 +1,793 @@
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+pub use analysis::*;
+pub use builder::*;
+pub use compiled::*;
+pub use debugger::*;
+pub use disassembly::*;
+pub use error::*;
+pub use expr::*;
+pub use halt::*;
+pub use instruction::*;
+pub use instruction_set::*;
+pub use optimizer::*;
+pub use registers::*;
+pub use snapshot::*;
+pub use stream::*;
+pub use symbolic::*;
+#[cfg(feature = "trace")]
+pub use trace::*;
+
+mod analysis;
+mod builder;
+mod compiled;
+mod debugger;
+mod disassembly;
+mod error;
+mod expr;
+#[cfg(test)]
+mod fuzz;
+mod halt;
+mod instruction;
+mod instruction_set;
+mod optimizer;
+mod registers;
+mod snapshot;
+mod stream;
+mod symbolic;
+#[cfg(feature = "trace")]
+mod trace;
+
+/// Environment variable naming the file that newly created computers append their trace to,
+/// when built with the `trace` feature.
+pub const TRACE_ENV_VAR: &str = "ASSEMBUNNY_TRACE";
+
+/// How a [`PrototypeComputer`] is set up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// Register names, each a lowercase ASCII letter.
+    pub registers: Vec<char>,
+    pub arithmetic: Arithmetic,
+    pub backend: Backend,
+}
+
+impl Default for Config {
+    /// Registers `a` to `d`, checked 32-bit arithmetic and the compiled backend.
+    fn default() -> Self {
+        Self {
+            registers: vec!['a', 'b', 'c', 'd'],
+            arithmetic: Arithmetic::default(),
+            backend: Backend::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PrototypeComputer {
+    registers: RegisterFile,
+    arithmetic: Arithmetic,
+    instruction_pointer: usize,
+    steps: u64,
+    // shared with snapshots until `tgl` rewrites it.
+    program: Rc<Vec<PrototypeComputerInstruction>>,
+    optimizer: Option<Optimizer>,
+    // `None` when running on the interpreter backend.
+    compiled: Option<Compiled>,
+    input: VecDeque<i64>,
+    // looked up by name before the built-in opcodes.
+    custom: Vec<&'static dyn Instruction>,
+    #[cfg(feature = "trace")]
+    tracer: Option<Tracer>,
+}
+
+#[cfg(feature = "trace")]
+impl Drop for PrototypeComputer {
+    fn drop(&mut self) {
+        if let Some(tracer) = &mut self.tracer {
+            tracer.finish(&self.program);
+        }
+    }
+}
+
+impl Default for PrototypeComputer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrototypeComputer {
+    /// Creates a computer with the [default](Config::default) configuration.
+    pub fn new() -> Self {
+        Self::with_config(&Config::default())
+    }
+
+    /// Creates a computer with the given register names, integer semantics and backend.
+    ///
+    /// # Panics
+    /// Panics if a register name is not a lowercase ASCII letter.
+    pub fn with_config(config: &Config) -> Self {
+        let Config {
+            registers,
+            arithmetic,
+            backend,
+        } = config;
+
+        Self {
+            registers: RegisterFile::new(registers),
+            arithmetic: *arithmetic,
+            instruction_pointer: 0,
+            steps: 0,
+            program: Rc::default(),
+            optimizer: None,
+            compiled: (*backend == Backend::Compiled).then(Compiled::default),
+            input: VecDeque::new(),
+            custom: Vec::new(),
+            #[cfg(feature = "trace")]
+            tracer: Tracer::from_env(),
+        }
+    }
+
+    /// Enables or disables executing recognized loop idioms as single arithmetic operations.
+    pub fn set_optimizations(&mut self, enabled: bool) {
+        self.optimizer = enabled.then(|| Optimizer::new(&self.program));
+    }
+
+    /// Adds an instruction to those the parser accepts, replacing any instruction of the same
+    /// name. Programs loaded afterwards may use it.
+    ///
+    /// # Panics
+    /// Panics if the instruction does not take one or two operands.
+    pub fn register_instruction(&mut self, definition: &'static dyn Instruction) {
+        assert!(
+            (1..=2).contains(&definition.arity()),
+            "`{}` must take one or two operands",
+            definition.name()
+        );
+
+        self.custom
+            .retain(|custom| custom.name() != definition.name());
+        self.custom.push(definition);
+    }
+
+    /// Looks up an opcode by its mnemonic among the registered and built-in instructions.
+    pub fn opcode(&self, name: &str) -> Option<Opcode> {
+        self.custom
+            .iter()
+            .find(|custom| custom.name() == name)
+            .map(|&custom| Opcode::Custom(custom))
+            .or_else(|| Opcode::from_name(name))
+    }
+
+    /// Switches between interpreting and executing the pre-decoded program.
+    pub fn set_backend(&mut self, backend: Backend) {
+        self.compiled = (backend == Backend::Compiled)
+            .then(|| Compiled::new(&self.program, &self.analyze_toggles_for_any_registers()));
+    }
+
+    pub fn backend(&self) -> Backend {
+        if self.compiled.is_some() {
+            Backend::Compiled
+        } else {
+            Backend::Interpreter
+        }
+    }
+
+    /// Replaces the tracer recording this computer's execution.
+    #[cfg(feature = "trace")]
+    pub fn set_tracer(&mut self, tracer: Option<Tracer>) {
+        self.tracer = tracer;
+    }
+
+    #[cfg(feature = "trace")]
+    pub fn tracer(&self) -> Option<&Tracer> {
+        self.tracer.as_ref()
+    }
+
+    pub fn get_register(&self, register: char) -> Option<i64> {
+        self.registers.get(register)
+    }
+
+    /// Sets a register, bringing the value into range according to the overflow mode.
+    pub fn set_register(&mut self, register: char, val: i64) -> Result<(), PrototypeComputerError> {
+        if !self.registers.contains(register) {
+            return Err(PrototypeComputerError::UnknownRegister(register));
+        }
+
+        let fitted =
+            self.arithmetic
+                .fit(i128::from(val))
+                .ok_or(PrototypeComputerError::OutOfRange {
+                    register,
+                    value: val,
+                })?;
+
+        self.registers.write(register, fitted);
+
+        Ok(())
+    }
+
+    pub fn registers(&self) -> &RegisterFile {
+        &self.registers
+    }
+
+    pub fn arithmetic(&self) -> Arithmetic {
+        self.arithmetic
+    }
+
+    pub fn instruction_pointer(&self) -> usize {
+        self.instruction_pointer
+    }
+
+    /// The loaded program, including any rewrites done by `tgl`.
+    pub fn program(&self) -> &[PrototypeComputerInstruction] {
+        &self.program
+    }
+
+    /// Number of steps executed so far. A fused optimizer block counts as one step.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Whether the instruction pointer has left the program.
+    pub fn is_halted(&self) -> bool {
+        self.instruction_pointer >= self.program.len()
+    }
+
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(r) => self.registers.read(r),
+            Operand::Immediate(v) => v,
+        }
+    }
+
+    /// Stores an exact result into a register, failing if it overflows in checked mode.
+    fn store(&mut self, register: char, val: i128) -> Result<(), OverflowError> {
+        self.store_slot(RegisterFile::slot(register), val)
+    }
+
+    fn store_slot(&mut self, slot: usize, val: i128) -> Result<(), OverflowError> {
+        let fitted = self.arithmetic.fit(val).ok_or_else(|| OverflowError {
+            index: self.instruction_pointer,
+            instruction: self.program[self.instruction_pointer],
+        })?;
+
+        self.registers.write_slot(slot, fitted);
+
+        Ok(())
+    }
+
+    fn offset(&self, by: i64) -> Option<usize> {
+        self.instruction_pointer
+            .checked_add_signed(isize::try_from(by).ok()?)
+    }
+
+    /// Executes the instruction at the instruction pointer, returning the value it sent via `out`.
+    ///
+    /// Instructions made invalid by `tgl` (e.g. `cpy 1 2`) are skipped. If the instruction
+    /// faults, the instruction pointer stays on it and the fault is returned as the error, as is
+    /// [`Halt::Exited`] if the computer [is halted](Self::is_halted).
+    pub fn step(&mut self) -> Result<Option<i64>, Halt> {
+        if self.is_halted() {
+            return Err(Halt::Exited);
+        }
+
+        #[cfg(feature = "trace")]
+        if self.tracer.is_some() {
+            return self.step_traced();
+        }
+
+        let output = if self.step_fused()? {
+            None
+        } else {
+            self.step_single()?
+        };
+
+        self.steps += 1;
+
+        Ok(output)
+    }
+
+    #[cfg(feature = "trace")]
+    fn step_traced(&mut self) -> Result<Option<i64>, Halt> {
+        let index = self.instruction_pointer;
+        let before = self.registers.clone();
+
+        let (label, output) = if self.step_fused()? {
+            let block = self.optimizer.as_ref().and_then(|o| o.block_at(index));
+            (block.map_or("fused", Fused::name), None)
+        } else {
+            let opcode = self.program[index].opcode;
+            (opcode.name(), self.step_single()?)
+        };
+
+        self.steps += 1;
+
+        if let Some(tracer) = &mut self.tracer {
+            tracer.record(index, label, output, &before, &self.registers);
+        }
+
+        Ok(output)
+    }
+
+    fn step_single(&mut self) -> Result<Option<i64>, Halt> {
+        match &self.compiled {
+            Some(compiled) => self.step_compiled(compiled.op_at(self.instruction_pointer)),
+            None => self.step_instruction(),
+        }
+    }
+
+    fn step_compiled(&mut self, op: Op) -> Result<Option<i64>, Halt> {
+        match op {
+            Op::CpyImm { val, dest } => self.store_slot(dest, i128::from(val))?,
+            Op::CpyReg { src, dest } => {
+                self.store_slot(dest, i128::from(self.registers.read_slot(src)))?;
+            }
+            Op::Inc(slot) => {
+                self.store_slot(slot, i128::from(self.registers.read_slot(slot)) + 1)?
+            }
+            Op::Dec(slot) => {
+                self.store_slot(slot, i128::from(self.registers.read_slot(slot)) - 1)?
+            }
+            Op::Jmp(target) => {
+                self.instruction_pointer = target;
+                return Ok(None);
+            }
+            Op::JnzReg { cond, target } if self.registers.read_slot(cond) != 0 => {
+                self.instruction_pointer = target;
+                return Ok(None);
+            }
+            Op::JnzReg { .. } | Op::Nop => {}
+            Op::Interpret => return self.step_instruction(),
+        }
+
+        self.instruction_pointer += 1;
+
+        Ok(None)
+    }
+
+    fn step_instruction(&mut self) -> Result<Option<i64>, Halt> {
+        let index = self.instruction_pointer;
+        let instruction = self.program[index];
+        let definition = instruction.opcode.definition();
+
+        if instruction.y.is_some() != (definition.arity() == 2) {
+            return Err(Halt::InvalidInstruction { index });
+        }
+
+        let mut machine = Machine {
+            registers: &mut self.registers,
+            arithmetic: self.arithmetic,
+            input: &mut self.input,
+            index,
+            instruction,
+        };
+
+        let mut output = None;
+
+        match definition.execute(&mut machine, instruction.x, instruction.y)? {
+            Effect::Next => {}
+            Effect::Jump(offset) => {
+                self.instruction_pointer = self
+                    .offset(offset)
+                    .ok_or(Halt::InvalidJump { index, offset })?;
+                return Ok(None);
+            }
+            Effect::Output(val) => output = Some(val),
+            Effect::Toggle(offset) => self.tgl(offset),
+        }
+
+        self.instruction_pointer += 1;
+
+        Ok(output)
+    }
+
+    /// Executes the fused block starting at the instruction pointer, if there is one and its
+    /// loop counters are positive. Otherwise the block is left to the regular interpreter.
+    ///
+    /// A block whose result overflows in checked mode is also left to the interpreter, so that
+    /// the overflow is reported at the same instruction and with the same registers.
+    fn step_fused(&mut self) -> Result<bool, OverflowError> {
+        let Some(&block) = self
+            .optimizer
+            .as_ref()
+            .and_then(|o| o.block_at(self.instruction_pointer))
+        else {
+            return Ok(false);
+        };
+
+        match block {
+            Fused::Add {
+                target,
+                counter,
+                delta,
+            } => {
+                let count = self.registers.read(counter);
+                if count <= 0 {
+                    return Ok(false);
+                }
+
+                let sum =
+                    i128::from(self.registers.read(target)) + i128::from(delta) * i128::from(count);
+                if self.arithmetic.fit(sum).is_none() {
+                    return Ok(false);
+                }
+
+                self.store(target, sum)?;
+                self.registers.write(counter, 0);
+            }
+            Fused::Multiply {
+                target,
+                source,
+                inner,
+                outer,
+                delta,
+            } => {
+                let (factor, count) = (self.value(source), self.registers.read(outer));
+                if factor <= 0 || count <= 0 {
+                    return Ok(false);
+                }
+
+                let product = i128::from(delta) * i128::from(factor) * i128::from(count);
+                let sum = i128::from(self.registers.read(target)) + product;
+                if self.arithmetic.fit(sum).is_none() {
+                    return Ok(false);
+                }
+
+                self.store(target, sum)?;
+                self.registers.write(inner, 0);
+                self.registers.write(outer, 0);
+            }
+        }
+
+        self.instruction_pointer += block.span();
+
+        Ok(true)
+    }
+
+    fn tgl(&mut self, offset: i64) {
+        let Some(index) = self.offset(offset).filter(|&i| i < self.program.len()) else {
+            return;
+        };
+
+        let instruction = &mut Rc::make_mut(&mut self.program)[index];
+        instruction.opcode = instruction.opcode.toggled();
+
+        if let Some(optimizer) = &mut self.optimizer {
+            optimizer.invalidate(&self.program, index);
+        }
+
+        if let Some(compiled) = &mut self.compiled {
+            compiled.recompile(&self.program, index);
+        }
+    }
+
+    fn parse_operand(&self, operand: &str) -> Option<Operand> {
+        if let Ok(val) = operand.parse::<i64>() {
+            return Some(Operand::Immediate(val));
+        }
+
+        let mut chars = operand.chars();
+        match (chars.next(), chars.next()) {
+            (Some(r), None) if self.registers.contains(r) => Some(Operand::Register(r)),
+            _ => None,
+        }
+    }
+
+    fn parse_instruction(
+        &self,
+        line: &str,
+    ) -> Result<PrototypeComputerInstruction, ParseErrorReason> {
+        let mut parts = line.split_whitespace();
+        let name = parts.next().unwrap_or_default();
+        let opcode = self
+            .opcode(name)
+            .ok_or_else(|| ParseErrorReason::UnknownOpcode(name.into()))?;
+
+        let operands = parts
+            .map(|s| {
+                self.parse_operand(s)
+                    .ok_or_else(|| ParseErrorReason::InvalidOperand(s.into()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if operands.len() != opcode.arity() {
+            return Err(ParseErrorReason::WrongArity {
+                expected: opcode.arity(),
+                found: operands.len(),
+            });
+        }
+
+        Ok(PrototypeComputerInstruction::new(
+            opcode,
+            operands[0],
+            operands.get(1).copied(),
+        ))
+    }
+
+    /// Decodes assembunny source lines. Blank lines are ignored.
+    pub fn parse_program(
+        &self,
+        program: &[&str],
+    ) -> Result<Vec<PrototypeComputerInstruction>, ParseError> {
+        program
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                self.parse_instruction(line).map_err(|reason| ParseError {
+                    line: i + 1,
+                    text: line.to_string(),
+                    reason,
+                })
+            })
+            .collect()
+    }
+
+    pub fn load_program(&mut self, program: &[&str]) -> Result<(), ParseError> {
+        let program = self.parse_program(program)?;
+        self.install(program);
+
+        Ok(())
+    }
+
+    /// Loads an already decoded program, e.g. one assembled with a [`ProgramBuilder`].
+    pub fn load_instructions(
+        &mut self,
+        program: Vec<PrototypeComputerInstruction>,
+    ) -> Result<(), PrototypeComputerError> {
+        self.check_registers(&program)?;
+        self.install(program);
+
+        Ok(())
+    }
+
+    /// Appends an instruction to the loaded program, keeping the registers and the instruction
+    /// pointer. A program that ran to its end resumes with the new instruction.
+    pub fn push_instruction(
+        &mut self,
+        instruction: PrototypeComputerInstruction,
+    ) -> Result<(), PrototypeComputerError> {
+        self.check_registers(&[instruction])?;
+        Rc::make_mut(&mut self.program).push(instruction);
+        self.reanalyze();
+
+        Ok(())
+    }
+
+    fn check_registers(
+        &self,
+        program: &[PrototypeComputerInstruction],
+    ) -> Result<(), PrototypeComputerError> {
+        let unknown = program
+            .iter()
+            .flat_map(|instruction| [Some(instruction.x), instruction.y])
+            .find_map(|operand| match operand {
+                Some(Operand::Register(r)) if !self.registers.contains(r) => Some(r),
+                _ => None,
+            });
+
+        match unknown {
+            Some(register) => Err(PrototypeComputerError::UnknownRegister(register)),
+            None => Ok(()),
+        }
+    }
+
+    fn install(&mut self, program: Vec<PrototypeComputerInstruction>) {
+        self.program = Rc::new(program);
+        self.instruction_pointer = 0;
+        self.reanalyze();
+    }
+
+    /// Rebuilds everything derived from the program after it was replaced.
+    fn reanalyze(&mut self) {
+        if let Some(optimizer) = &mut self.optimizer {
+            *optimizer = Optimizer::new(&self.program);
+        }
+
+        if self.compiled.is_some() {
+            let toggles = self.analyze_toggles_for_any_registers();
+            self.compiled = Some(Compiled::new(&self.program, &toggles));
+        }
+    }
+
+    /// Runs until the program exits, sends a value, faults or exhausts the budget.
+    pub fn run(&mut self, budget: Budget) -> Halt {
+        self.run_limited(Limit::new(budget, self.steps))
+    }
+
+    fn run_limited(&mut self, limit: Limit) -> Halt {
+        loop {
+            if self.is_halted() {
+                return Halt::Exited;
+            }
+
+            if limit.is_exhausted(self.steps) {
+                return Halt::BudgetExhausted;
+            }
+
+            match self.step() {
+                Ok(Some(val)) => return Halt::Output(val),
+                Ok(None) => {}
+                Err(halt) => return halt,
+            }
+        }
+    }
+
+    /// Runs until the next `out`, returning how the computer halted if it never got there.
+    pub fn run_to_next_output(&mut self) -> Result<i64, Halt> {
+        match self.run(Budget::unlimited()) {
+            Halt::Output(val) => Ok(val),
+            halt => Err(halt),
+        }
+    }
+
+    /// Loads and runs a program to its end, discarding any values it sends.
+    pub fn run_program(&mut self, program: &[&str]) -> Result<(), PrototypeComputerError> {
+        self.load_program(program)?;
+
+        loop {
+            match self.run(Budget::unlimited()) {
+                Halt::Exited => return Ok(()),
+                Halt::Output(_) => {}
+                halt => return Err(PrototypeComputerError::Fault(halt)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_typed_instructions() {
+        let computer = PrototypeComputer::new();
+        let program = computer
+            .parse_program(&["cpy 41 a", "", "jnz a -2"])
+            .unwrap();
+
+        assert_eq!(
+            program,
+            vec![
+                PrototypeComputerInstruction::new(
+                    Opcode::Cpy,
+                    Operand::Immediate(41),
+                    Some(Operand::Register('a'))
+                ),
+                PrototypeComputerInstruction::new(
+                    Opcode::Jnz,
+                    Operand::Register('a'),
+                    Some(Operand::Immediate(-2))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        let computer = PrototypeComputer::new();
+
+        let err = computer.parse_program(&["inc a", "mul a b"]).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.text, "mul a b");
+        assert_eq!(err.reason, ParseErrorReason::UnknownOpcode("mul".into()));
+
+        let err = computer.parse_program(&["cpy 1"]).unwrap_err();
+        assert_eq!(
+            err.reason,
+            ParseErrorReason::WrongArity {
+                expected: 2,
+                found: 1
+            }
+        );
+
+        let err = computer.parse_program(&["inc e"]).unwrap_err();
+        assert_eq!(err.reason, ParseErrorReason::InvalidOperand("e".into()));
+    }
+
+    #[test]
+    fn skips_instructions_invalidated_by_tgl() {
+        let mut computer = PrototypeComputer::new();
+        computer
+            .run_program(&["tgl 1", "jnz 1 2", "inc a"])
+            .unwrap();
+
+        assert_eq!(computer.get_register('a'), Some(1));
+    }
+
+    #[test]
+    fn handles_overflow_by_mode() {
+        let program = ["cpy 2147483647 a", "inc a"];
+
+        let mut computer = PrototypeComputer::new();
+        let err = computer.run_program(&program).unwrap_err();
+        assert_eq!(
+            err,
+            PrototypeComputerError::Fault(Halt::Overflow(OverflowError {
+                index: 1,
+                instruction: computer.program()[1]
+            }))
+        );
+
+        for (arithmetic, expected) in [
+            (
+                Arithmetic::new(Width::I32, Overflow::Wrapping),
+                i64::from(i32::MIN),
+            ),
+            (
+                Arithmetic::new(Width::I32, Overflow::Saturating),
+                i64::from(i32::MAX),
+            ),
+            (Arithmetic::new(Width::I64, Overflow::Checked), 1 << 31),
+        ] {
+            let mut computer = PrototypeComputer::with_config(&Config {
+                registers: vec!['a'],
+                arithmetic,
+                ..Config::default()
+            });
+            computer.run_program(&program).unwrap();
+            assert_eq!(computer.get_register('a'), Some(expected));
+        }
+    }
+
+    #[test]
+    fn reports_halting_reasons() {
+        let mut computer = PrototypeComputer::new();
+        computer
+            .load_program(&["inc a", "out a", "jnz 1 -2"])
+            .unwrap();
+
+        assert_eq!(computer.run(Budget::steps(1)), Halt::BudgetExhausted);
+        assert_eq!(computer.run(Budget::unlimited()), Halt::Output(1));
+        assert_eq!(computer.run(Budget::steps(100)), Halt::Output(2));
+        assert_eq!(computer.run(Budget::steps(100)), Halt::Output(3));
+        assert_eq!(computer.steps(), 8);
+
+        computer.load_program(&["jnz 1 -1"]).unwrap();
+        assert_eq!(
+            computer.run(Budget::unlimited()),
+            Halt::InvalidJump {
+                index: 0,
+                offset: -1
+            }
+        );
+        assert_eq!(computer.instruction_pointer(), 0);
+
+        computer.load_program(&["cpy 1 a"]).unwrap();
+        assert_eq!(computer.run(Budget::unlimited()), Halt::Exited);
+        assert_eq!(computer.step(), Err(Halt::Exited));
+    }
+
+    #[test]
+    fn rejects_instructions_with_missing_operands() {
+        let mut computer = PrototypeComputer::new();
+        computer.load_program(&["inc a"]).unwrap();
+        Rc::make_mut(&mut computer.program)[0] =
+            PrototypeComputerInstruction::new(Opcode::Cpy, Operand::Immediate(1), None);
+        computer.reanalyze();
+
+        assert_eq!(
+            computer.run(Budget::unlimited()),
+            Halt::InvalidInstruction { index: 0 }
+        );
+    }
+
+    #[test]
+    fn uses_configured_register_names() {
+        let mut computer = PrototypeComputer::with_config(&Config {
+            registers: vec!['x', 'a'],
+            ..Config::default()
+        });
+
+        assert_eq!(computer.registers().names(), &['a', 'x']);
+        assert_eq!(
+            computer.set_register('b', 1),
+            Err(PrototypeComputerError::UnknownRegister('b'))
+        );
+
+        computer.run_program(&["cpy 3 x", "inc x"]).unwrap();
+        assert_eq!(computer.get_register('x'), Some(4));
+        assert!(computer.parse_program(&["inc b"]).is_err());
+    }
+}
@@ -0,0 +1,260 @@
+use super::{Opcode, Operand, PrototypeComputerInstruction};
+
+/// Longest instruction sequence any [`Fused`] block covers.
+const MAX_BLOCK_LEN: usize = 6;
+
+/// A loop idiom that can be executed as a single arithmetic operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fused {
+    /// `inc target; dec counter; jnz counter -2` (in either order), i.e. `target += delta * counter`.
+    Add {
+        target: char,
+        counter: char,
+        delta: i64,
+    },
+    /// `cpy source inner; <add loop of inner into target>; dec outer; jnz outer -5`,
+    /// i.e. `target += delta * source * outer`.
+    Multiply {
+        target: char,
+        source: Operand,
+        inner: char,
+        outer: char,
+        delta: i64,
+    },
+}
+
+impl Fused {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Fused::Add { .. } => "add",
+            Fused::Multiply { .. } => "mul",
+        }
+    }
+
+    /// Number of instructions the block replaces.
+    pub fn span(&self) -> usize {
+        match self {
+            Fused::Add { .. } => 3,
+            Fused::Multiply { .. } => 6,
+        }
+    }
+
+    fn detect(window: &[PrototypeComputerInstruction]) -> Option<Self> {
+        Self::detect_multiply(window).or_else(|| Self::detect_add(window))
+    }
+
+    fn detect_add(window: &[PrototypeComputerInstruction]) -> Option<Self> {
+        let [first, second, jump, ..] = window else {
+            return None;
+        };
+
+        let (step, dec) = if second.opcode == Opcode::Dec {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        let (Operand::Register(target), Operand::Register(counter)) = (step.x, dec.x) else {
+            return None;
+        };
+
+        let delta = match step.opcode {
+            Opcode::Inc => 1,
+            Opcode::Dec => -1,
+            _ => return None,
+        };
+
+        let is_loop = dec.opcode == Opcode::Dec
+            && jump.opcode == Opcode::Jnz
+            && jump.x == Operand::Register(counter)
+            && jump.y == Some(Operand::Immediate(-2));
+
+        (is_loop && target != counter).then_some(Fused::Add {
+            target,
+            counter,
+            delta,
+        })
+    }
+
+    fn detect_multiply(window: &[PrototypeComputerInstruction]) -> Option<Self> {
+        let [copy, body @ .., dec, jump] = window.get(..MAX_BLOCK_LEN)? else {
+            return None;
+        };
+
+        let Some(Fused::Add {
+            target,
+            counter: inner,
+            delta,
+        }) = Self::detect_add(body)
+        else {
+            return None;
+        };
+
+        let Operand::Register(outer) = dec.x else {
+            return None;
+        };
+
+        let is_loop = copy.opcode == Opcode::Cpy
+            && copy.y == Some(Operand::Register(inner))
+            && dec.opcode == Opcode::Dec
+            && jump.opcode == Opcode::Jnz
+            && jump.x == Operand::Register(outer)
+            && jump.y == Some(Operand::Immediate(-5));
+
+        let is_independent = outer != target
+            && outer != inner
+            && ![target, inner, outer]
+                .iter()
+                .any(|&r| copy.x == Operand::Register(r));
+
+        (is_loop && is_independent).then_some(Fused::Multiply {
+            target,
+            source: copy.x,
+            inner,
+            outer,
+            delta,
+        })
+    }
+}
+
+/// Peephole optimizer that maps instruction indices to the [`Fused`] block starting there.
+///
+/// Blocks are re-derived whenever `tgl` rewrites an instruction, so they always reflect the
+/// program as it currently is.
+#[derive(Debug, Clone, Default)]
+pub struct Optimizer {
+    blocks: Vec<Option<Fused>>,
+}
+
+impl Optimizer {
+    pub fn new(program: &[PrototypeComputerInstruction]) -> Self {
+        Self {
+            blocks: (0..program.len())
+                .map(|i| Fused::detect(&program[i..]))
+                .collect(),
+        }
+    }
+
+    /// The fused block starting at `index`, if any.
+    pub fn block_at(&self, index: usize) -> Option<&Fused> {
+        self.blocks.get(index)?.as_ref()
+    }
+
+    /// Re-derives every block that could contain the (possibly toggled) instruction at `index`.
+    pub fn invalidate(&mut self, program: &[PrototypeComputerInstruction], index: usize) {
+        let start = index.saturating_sub(MAX_BLOCK_LEN - 1);
+
+        for i in start..=index.min(program.len().saturating_sub(1)) {
+            self.blocks[i] = Fused::detect(&program[i..]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PrototypeComputer;
+
+    fn blocks(program: &[&str]) -> Optimizer {
+        Optimizer::new(&PrototypeComputer::new().parse_program(program).unwrap())
+    }
+
+    #[test]
+    fn detects_add_loops() {
+        let optimizer = blocks(&["cpy 3 b", "dec b", "inc a", "jnz b -2"]);
+
+        assert_eq!(optimizer.block_at(0), None);
+        assert_eq!(
+            optimizer.block_at(1),
+            Some(&Fused::Add {
+                target: 'a',
+                counter: 'b',
+                delta: 1
+            })
+        );
+    }
+
+    #[test]
+    fn detects_multiply_loops() {
+        let optimizer = blocks(&["cpy b c", "inc a", "dec c", "jnz c -2", "dec d", "jnz d -5"]);
+
+        assert_eq!(
+            optimizer.block_at(0),
+            Some(&Fused::Multiply {
+                target: 'a',
+                source: Operand::Register('b'),
+                inner: 'c',
+                outer: 'd',
+                delta: 1
+            })
+        );
+    }
+
+    #[test]
+    fn matches_unoptimized_results() {
+        let program = [
+            "cpy 4 b", "cpy 3 d", "cpy b c", "inc a", "dec c", "jnz c -2", "dec d", "jnz d -5",
+            "cpy 5 c", "dec c", "inc b", "jnz c -2",
+        ];
+
+        let mut reference = PrototypeComputer::new();
+        reference.run_program(&program).unwrap();
+
+        let mut optimized = PrototypeComputer::new();
+        optimized.set_optimizations(true);
+        optimized.run_program(&program).unwrap();
+
+        for register in ['a', 'b', 'c', 'd'] {
+            assert_eq!(
+                optimized.get_register(register),
+                reference.get_register(register)
+            );
+        }
+        assert_eq!(optimized.get_register('a'), Some(12));
+    }
+
+    #[test]
+    fn overflows_like_unoptimized_loops() {
+        let program = ["cpy 2147483640 a", "cpy 10 b", "dec b", "inc a", "jnz b -2"];
+
+        let mut reference = PrototypeComputer::new();
+        let expected = reference.run_program(&program).unwrap_err();
+
+        let mut optimized = PrototypeComputer::new();
+        optimized.set_optimizations(true);
+        assert_eq!(optimized.run_program(&program).unwrap_err(), expected);
+
+        for register in ['a', 'b'] {
+            assert_eq!(
+                optimized.get_register(register),
+                reference.get_register(register)
+            );
+        }
+        assert_eq!(optimized.get_register('b'), Some(2));
+    }
+
+    #[test]
+    fn drops_blocks_broken_by_tgl() {
+        let program = ["cpy 3 b", "cpy 3 c", "tgl c", "inc a", "dec b", "jnz b -2"];
+
+        let mut computer = PrototypeComputer::new();
+        computer.set_optimizations(true);
+        computer.run_program(&program).unwrap();
+
+        assert_eq!(computer.get_register('a'), Some(1));
+        assert_eq!(computer.get_register('b'), Some(2));
+    }
+
+    #[test]
+    fn derives_blocks_created_by_tgl() {
+        let program = ["cpy 3 b", "tgl 3", "inc a", "dec b", "cpy b -2"];
+        assert_eq!(blocks(&program).block_at(2), None);
+
+        let mut computer = PrototypeComputer::new();
+        computer.set_optimizations(true);
+        computer.run_program(&program).unwrap();
+
+        assert!(computer.optimizer.as_ref().unwrap().block_at(2).is_some());
+        assert_eq!(computer.get_register('a'), Some(3));
+    }
+}
@@ -0,0 +1,137 @@
+/// Registers are named by lowercase letters, each with a fixed slot.
+const SLOTS: usize = 26;
+
+/// Integer width of the registers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Width {
+    #[default]
+    I32,
+    I64,
+}
+
+impl Width {
+    /// Smallest and largest value a register can hold.
+    pub fn bounds(self) -> (i64, i64) {
+        match self {
+            Width::I32 => (i64::from(i32::MIN), i64::from(i32::MAX)),
+            Width::I64 => (i64::MIN, i64::MAX),
+        }
+    }
+}
+
+/// What happens when an arithmetic result does not fit into the register [`Width`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Overflow {
+    /// The instruction fails with an [`OverflowError`](super::OverflowError).
+    #[default]
+    Checked,
+    Wrapping,
+    Saturating,
+}
+
+/// Integer semantics of a [`PrototypeComputer`](super::PrototypeComputer).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Arithmetic {
+    pub width: Width,
+    pub overflow: Overflow,
+}
+
+impl Arithmetic {
+    pub fn new(width: Width, overflow: Overflow) -> Self {
+        Self { width, overflow }
+    }
+
+    /// Brings an exact result into range, or returns `None` if it overflows in checked mode.
+    pub fn fit(self, val: i128) -> Option<i64> {
+        let (min, max) = self.width.bounds();
+        let (min, max) = (i128::from(min), i128::from(max));
+
+        if (min..=max).contains(&val) {
+            // NOTE: the range check above makes this lossless.
+            #[allow(clippy::cast_possible_truncation)]
+            return Some(val as i64);
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        match (self.overflow, self.width) {
+            (Overflow::Checked, _) => None,
+            (Overflow::Wrapping, Width::I32) => Some(i64::from(val as i32)),
+            (Overflow::Wrapping, Width::I64) => Some(val as i64),
+            (Overflow::Saturating, _) => Some(val.clamp(min, max) as i64),
+        }
+    }
+}
+
+/// A register file whose names are chosen when the computer is built.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RegisterFile {
+    values: [i64; SLOTS],
+    names: Vec<char>,
+}
+
+impl Default for RegisterFile {
+    fn default() -> Self {
+        Self::new(&['a', 'b', 'c', 'd'])
+    }
+}
+
+impl RegisterFile {
+    /// Creates a zeroed register file.
+    ///
+    /// # Panics
+    /// Panics if a name is not a lowercase ASCII letter.
+    pub fn new(names: &[char]) -> Self {
+        assert!(
+            names.iter().all(char::is_ascii_lowercase),
+            "register names must be lowercase ASCII letters, got {names:?}"
+        );
+
+        let mut names = names.to_vec();
+        names.sort_unstable();
+        names.dedup();
+
+        Self {
+            values: [0; SLOTS],
+            names,
+        }
+    }
+
+    pub(super) fn slot(register: char) -> usize {
+        (register as u8 - b'a') as usize
+    }
+
+    pub fn names(&self) -> &[char] {
+        &self.names
+    }
+
+    pub fn contains(&self, register: char) -> bool {
+        self.names.binary_search(&register).is_ok()
+    }
+
+    pub fn get(&self, register: char) -> Option<i64> {
+        self.contains(register)
+            .then(|| self.values[Self::slot(register)])
+    }
+
+    /// All registers with their current values, in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (char, i64)> + '_ {
+        self.names.iter().map(|&r| (r, self.values[Self::slot(r)]))
+    }
+
+    /// Reads a register that is known to exist, e.g. because the parser validated it.
+    pub(super) fn read(&self, register: char) -> i64 {
+        self.values[Self::slot(register)]
+    }
+
+    pub(super) fn write(&mut self, register: char, val: i64) {
+        self.values[Self::slot(register)] = val;
+    }
+
+    pub(super) fn read_slot(&self, slot: usize) -> i64 {
+        self.values[slot]
+    }
+
+    pub(super) fn write_slot(&mut self, slot: usize, val: i64) {
+        self.values[slot] = val;
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+
+use super::{Budget, Halt, Limit, PrototypeComputer, PrototypeComputerInstruction, RegisterFile};
+
+/// The full state of a [`PrototypeComputer`]: registers, instruction pointer, pending input and
+/// the (possibly toggled) program. Taking a snapshot does not copy the program.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Snapshot {
+    registers: RegisterFile,
+    instruction_pointer: usize,
+    input: VecDeque<i64>,
+    program: Rc<Vec<PrototypeComputerInstruction>>,
+}
+
+/// An output stream proven to be periodic: `prefix` is sent once, then `period` repeats forever.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputCycle {
+    pub prefix: Vec<i64>,
+    pub period: Vec<i64>,
+}
+
+impl OutputCycle {
+    /// The `n`-th value of the infinite output stream.
+    pub fn nth(&self, n: usize) -> i64 {
+        match self.prefix.get(n) {
+            Some(&val) => val,
+            None => self.period[(n - self.prefix.len()) % self.period.len()],
+        }
+    }
+}
+
+impl PrototypeComputer {
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            registers: self.registers.clone(),
+            instruction_pointer: self.instruction_pointer,
+            input: self.input.clone(),
+            program: Rc::clone(&self.program),
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.registers.clone_from(&snapshot.registers);
+        self.instruction_pointer = snapshot.instruction_pointer;
+        self.input.clone_from(&snapshot.input);
+
+        if !Rc::ptr_eq(&self.program, &snapshot.program) {
+            self.program = Rc::clone(&snapshot.program);
+            self.reanalyze();
+        }
+    }
+
+    /// Runs the program until the machine is in a state it was already in right after an
+    /// `out`. Since execution is deterministic, the values sent between the two visits repeat
+    /// forever.
+    ///
+    /// Returns `None` if the program halts, faults, exhausts the budget or sends `max_outputs`
+    /// values without repeating. The budget covers the whole search.
+    pub fn find_output_cycle(&mut self, max_outputs: usize, budget: Budget) -> Option<OutputCycle> {
+        let limit = Limit::new(budget, self.steps);
+        let mut seen: HashMap<Snapshot, usize> = HashMap::new();
+        let mut outputs = vec![];
+
+        while outputs.len() < max_outputs {
+            let Halt::Output(val) = self.run_limited(limit) else {
+                return None;
+            };
+
+            outputs.push(val);
+
+            if let Some(start) = seen.insert(self.snapshot(), outputs.len()) {
+                let period = outputs.split_off(start);
+                return Some(OutputCycle {
+                    prefix: outputs,
+                    period,
+                });
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restores_toggled_programs() {
+        let mut computer = PrototypeComputer::new();
+        computer.load_program(&["tgl 1", "inc a", "inc a"]).unwrap();
+
+        let snapshot = computer.snapshot();
+        while !computer.is_halted() {
+            computer.step().unwrap();
+        }
+        assert_eq!(computer.get_register('a'), Some(0));
+
+        computer.restore(&snapshot);
+        assert_eq!(computer.snapshot(), snapshot);
+        assert_eq!(computer.program()[1].to_string(), "inc a");
+    }
+
+    #[test]
+    fn finds_output_cycles() {
+        let mut computer = PrototypeComputer::new();
+        computer
+            .load_program(&["out 7", "cpy 0 b", "out b", "inc b", "out b", "jnz 1 -4"])
+            .unwrap();
+
+        let cycle = computer
+            .find_output_cycle(100, Budget::unlimited())
+            .unwrap();
+        assert_eq!(cycle.prefix, vec![7, 0]);
+        assert_eq!(cycle.period, vec![1, 0]);
+        assert_eq!(cycle.nth(4), 1);
+        assert_eq!(cycle.nth(5), 0);
+    }
+
+    #[test]
+    fn gives_up_on_halting_programs() {
+        let mut computer = PrototypeComputer::new();
+        computer.load_program(&["out 1", "out 2"]).unwrap();
+
+        assert_eq!(computer.find_output_cycle(100, Budget::unlimited()), None);
+    }
+
+    #[test]
+    fn gives_up_when_budget_runs_out() {
+        let mut computer = PrototypeComputer::new();
+        computer.load_program(&["inc a", "jnz 1 -1"]).unwrap();
+
+        assert_eq!(computer.find_output_cycle(100, Budget::steps(1000)), None);
+        assert_eq!(computer.steps(), 1000);
+    }
+}
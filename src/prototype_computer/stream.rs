@@ -0,0 +1,120 @@
+use std::iter::FusedIterator;
+
+use super::{Budget, Halt, Limit, PrototypeComputer};
+
+/// Iterator over the values a [`PrototypeComputer`] sends via `out`, created by
+/// [`PrototypeComputer::outputs`].
+///
+/// Iteration ends when the program exits, faults or exhausts the budget; [`Outputs::halt`] tells
+/// which. The computer keeps its state, so a new iterator resumes where this one stopped.
+#[derive(Debug)]
+pub struct Outputs<'a> {
+    computer: &'a mut PrototypeComputer,
+    limit: Limit,
+    halt: Option<Halt>,
+}
+
+impl Outputs<'_> {
+    /// Why the stream ended, or `None` while it may still yield values.
+    pub fn halt(&self) -> Option<Halt> {
+        self.halt
+    }
+}
+
+impl Iterator for Outputs<'_> {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.halt.is_some() {
+            return None;
+        }
+
+        match self.computer.run_limited(self.limit) {
+            Halt::Output(val) => Some(val),
+            halt => {
+                self.halt = Some(halt);
+                None
+            }
+        }
+    }
+}
+
+impl FusedIterator for Outputs<'_> {}
+
+impl PrototypeComputer {
+    /// Runs the program lazily, yielding each value it sends. The budget covers the whole
+    /// iteration.
+    pub fn outputs(&mut self, budget: Budget) -> Outputs<'_> {
+        Outputs {
+            limit: Limit::new(budget, self.steps),
+            computer: self,
+            halt: None,
+        }
+    }
+
+    /// Queues a value for the program to read.
+    pub fn push_input(&mut self, val: i64) {
+        self.input.push_back(val);
+    }
+
+    /// Values queued but not read yet, oldest first.
+    pub fn pending_input(&self) -> impl Iterator<Item = i64> + '_ {
+        self.input.iter().copied()
+    }
+
+    /// Takes the oldest queued value, as an input instruction would.
+    pub fn read_input(&mut self) -> Option<i64> {
+        self.input.pop_front()
+    }
+}
+
+impl Extend<i64> for PrototypeComputer {
+    fn extend<T: IntoIterator<Item = i64>>(&mut self, iter: T) {
+        self.input.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streams_outputs_until_halt() {
+        let mut computer = PrototypeComputer::new();
+        computer
+            .load_program(&["out 1", "out 2", "cpy 3 a", "out a"])
+            .unwrap();
+
+        let mut outputs = computer.outputs(Budget::unlimited());
+        assert_eq!(outputs.by_ref().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(outputs.halt(), Some(Halt::Exited));
+        assert_eq!(outputs.next(), None);
+    }
+
+    #[test]
+    fn resumes_after_budget_runs_out() {
+        let mut computer = PrototypeComputer::new();
+        computer
+            .load_program(&["out a", "inc a", "jnz 1 -2"])
+            .unwrap();
+
+        let mut outputs = computer.outputs(Budget::steps(7));
+        assert_eq!(outputs.by_ref().count(), 3);
+        assert_eq!(outputs.halt(), Some(Halt::BudgetExhausted));
+
+        assert!(computer
+            .outputs(Budget::unlimited())
+            .take(4)
+            .eq([3, 4, 5, 6]));
+    }
+
+    #[test]
+    fn queues_input() {
+        let mut computer = PrototypeComputer::new();
+        computer.push_input(1);
+        computer.extend([2, 3]);
+
+        assert_eq!(computer.read_input(), Some(1));
+        assert_eq!(computer.pending_input().collect::<Vec<_>>(), vec![2, 3]);
+    }
+}
@@ -0,0 +1,946 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+
+use super::{
+    Arithmetic, Expr, Fused, Halt, Interval, Opcode, Operand, Optimizer, OverflowError,
+    PrototypeComputer, PrototypeComputerInstruction, RegisterFile,
+};
+
+/// Upper bound of paths explored before the remaining input space is left unresolved.
+const MAX_PATHS: usize = 64;
+
+/// Largest domain of a single symbol whose values are tried one by one to decide a non-linear
+/// constraint.
+const MAX_ENUMERATED: i128 = 64;
+
+/// Upper bound of steps on a single path. Loops whose trip count depends on a symbol never
+/// finish, so they are cut off here.
+const MAX_STEPS: u64 = 1_000_000;
+
+/// How an expression relates to zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Relation {
+    Zero,
+    NonZero,
+    Positive,
+    NonPositive,
+}
+
+impl Relation {
+    pub fn negate(self) -> Self {
+        match self {
+            Relation::Zero => Relation::NonZero,
+            Relation::NonZero => Relation::Zero,
+            Relation::Positive => Relation::NonPositive,
+            Relation::NonPositive => Relation::Positive,
+        }
+    }
+
+    pub fn holds(self, val: i128) -> bool {
+        match self {
+            Relation::Zero => val == 0,
+            Relation::NonZero => val != 0,
+            Relation::Positive => val > 0,
+            Relation::NonPositive => val <= 0,
+        }
+    }
+
+    /// What knowing that an expression satisfies `self` says about `other`.
+    fn implies(self, other: Self) -> Option<bool> {
+        match (self, other) {
+            _ if self == other => Some(true),
+            _ if self.negate() == other => Some(false),
+            (Relation::Zero, Relation::Positive) | (Relation::Positive, Relation::Zero) => {
+                Some(false)
+            }
+            (Relation::Zero, Relation::NonPositive) | (Relation::Positive, Relation::NonZero) => {
+                Some(true)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A condition on the symbols, e.g. `a - 1 > 0`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Constraint {
+    pub expr: Expr,
+    pub relation: Relation,
+}
+
+impl Display for Constraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let op = match self.relation {
+            Relation::Zero => "==",
+            Relation::NonZero => "!=",
+            Relation::Positive => ">",
+            Relation::NonPositive => "<=",
+        };
+
+        write!(f, "{} {op} 0", self.expr)
+    }
+}
+
+/// The values a symbol may still take.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Domain {
+    pub min: i128,
+    pub max: i128,
+    pub excluded: BTreeSet<i128>,
+}
+
+impl Domain {
+    pub fn contains(&self, val: i128) -> bool {
+        (self.min..=self.max).contains(&val) && !self.excluded.contains(&val)
+    }
+
+    fn single(&self) -> Option<i128> {
+        (self.min == self.max).then_some(self.min)
+    }
+
+    /// All values, if there are few enough to try each of them.
+    fn values(&self) -> Option<Vec<i128>> {
+        (self.max - self.min < MAX_ENUMERATED).then(|| {
+            (self.min..=self.max)
+                .filter(|val| !self.excluded.contains(val))
+                .collect()
+        })
+    }
+
+    /// Narrows the domain to the values for which `k * s + m` satisfies the relation. Returns
+    /// whether any value is left, or `None` without narrowing if the bounds would overflow.
+    fn restrict(&mut self, k: i128, m: i128, relation: Relation) -> Option<bool> {
+        let neg_m = m.checked_neg()?;
+        // a remainder that does not overflow means the quotient does not either.
+        let root = (neg_m.checked_rem(k)? == 0).then(|| neg_m / k);
+
+        match relation {
+            Relation::Zero => {
+                let Some(root) = root.filter(|&r| self.contains(r)) else {
+                    return Some(false);
+                };
+                (self.min, self.max) = (root, root);
+            }
+            Relation::NonZero => {
+                if let Some(root) = root {
+                    self.excluded.insert(root);
+                }
+            }
+            // k * s + m > 0
+            Relation::Positive if k > 0 => {
+                let min = neg_m.checked_div_euclid(k)?.checked_add(1)?;
+                self.min = self.min.max(min);
+            }
+            Relation::Positive => {
+                let max = m.checked_sub(1)?.checked_div_euclid(k.checked_neg()?)?;
+                self.max = self.max.min(max);
+            }
+            // k * s + m <= 0, i.e. -k * s - m + 1 > 0
+            Relation::NonPositive => {
+                return self.restrict(k.checked_neg()?, 1i128.checked_sub(m)?, Relation::Positive)
+            }
+        }
+
+        while self.min <= self.max && self.excluded.remove(&self.min) {
+            self.min += 1;
+        }
+
+        while self.min <= self.max && self.excluded.remove(&self.max) {
+            self.max -= 1;
+        }
+
+        self.excluded
+            .retain(|val| (self.min..=self.max).contains(val));
+
+        Some(self.min <= self.max)
+    }
+
+    /// Decides whether `k * s + m` satisfies the relation for every value of the domain, or for
+    /// none. `None` if it depends on the value, or if the bounds would overflow.
+    fn decide(&self, k: i128, m: i128, relation: Relation) -> Option<bool> {
+        let (a, b) = (
+            k.checked_mul(self.min)?.checked_add(m)?,
+            k.checked_mul(self.max)?.checked_add(m)?,
+        );
+        let (lo, hi) = (a.min(b), a.max(b));
+
+        let neg_m = m.checked_neg()?;
+        let never_zero =
+            lo > 0 || hi < 0 || neg_m.checked_rem(k)? != 0 || !self.contains(neg_m / k);
+
+        let decided = match relation {
+            Relation::Positive | Relation::NonPositive if lo > 0 => Some(true),
+            Relation::Positive | Relation::NonPositive if hi <= 0 => Some(false),
+            Relation::Zero | Relation::NonZero if never_zero => Some(false),
+            Relation::Zero | Relation::NonZero if lo == 0 && hi == 0 => Some(true),
+            _ => None,
+        };
+
+        // the arms above answer for `Positive` and `Zero`.
+        decided.map(|holds| match relation {
+            Relation::Positive | Relation::Zero => holds,
+            Relation::NonPositive | Relation::NonZero => !holds,
+        })
+    }
+}
+
+/// Everything known about the symbols on one path through the program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assumptions {
+    bounds: (i128, i128),
+    domains: BTreeMap<char, Domain>,
+    // constraints that do not fit into a single domain.
+    constraints: Vec<Constraint>,
+}
+
+impl Assumptions {
+    fn new(symbols: &[(char, Interval)], arithmetic: Arithmetic) -> Self {
+        let (min, max) = arithmetic.width.bounds();
+        let bounds = (i128::from(min), i128::from(max));
+
+        let domains = symbols
+            .iter()
+            .map(|&(s, interval)| {
+                let domain = Domain {
+                    min: i128::from(interval.min),
+                    max: i128::from(interval.max),
+                    excluded: BTreeSet::new(),
+                };
+                (s, domain)
+            })
+            .collect();
+
+        Self {
+            bounds,
+            domains,
+            constraints: vec![],
+        }
+    }
+
+    pub fn domain(&self, symbol: char) -> Option<&Domain> {
+        self.domains.get(&symbol)
+    }
+
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Whether concrete symbol values satisfy these assumptions.
+    pub fn holds(&self, value_of: impl Fn(char) -> Option<i128>) -> bool {
+        self.domains
+            .iter()
+            .all(|(&s, domain)| value_of(s).is_some_and(|val| domain.contains(val)))
+            && self.constraints.iter().all(|c| {
+                c.expr
+                    .evaluate(&value_of)
+                    .is_some_and(|val| c.relation.holds(val))
+            })
+    }
+
+    /// Decides a relation from the assumptions alone, if possible.
+    fn decide(&self, expr: &Expr, relation: Relation) -> Option<bool> {
+        if let Some(val) = expr.as_constant() {
+            return Some(relation.holds(val));
+        }
+
+        if let Some((s, k, m)) = expr.as_linear() {
+            let decided = self.domains.get(&s)?.decide(k, m, relation);
+            if decided.is_some() {
+                return decided;
+            }
+        }
+
+        if let Some(results) = self.enumerate(expr, relation) {
+            if results.iter().all(|&(_, holds)| holds) {
+                return Some(true);
+            }
+            if results.iter().all(|&(_, holds)| !holds) {
+                return Some(false);
+            }
+        }
+
+        self.constraints
+            .iter()
+            .filter(|c| &c.expr == expr)
+            .find_map(|c| c.relation.implies(relation))
+    }
+
+    /// Whether the relation holds for each value of the only symbol of `expr`, if its domain is
+    /// small.
+    fn enumerate(&self, expr: &Expr, relation: Relation) -> Option<Vec<(i128, bool)>> {
+        let mut symbols = expr.symbols();
+        let (s, None) = (symbols.next()?, symbols.next()) else {
+            return None;
+        };
+
+        let values = self.domains.get(&s)?.values()?;
+        values
+            .into_iter()
+            .map(|val| {
+                let result = expr.substitute(s, val)?.as_constant()?;
+                Some((val, relation.holds(result)))
+            })
+            .collect()
+    }
+
+    /// Adds a constraint. Returns the symbols that are now pinned to a single value, or `None`
+    /// if the assumptions became contradictory.
+    fn assume(&mut self, expr: Expr, relation: Relation) -> Option<Vec<(char, i128)>> {
+        let mut pinned = vec![];
+        let mut pending = vec![Constraint { expr, relation }];
+
+        while let Some(constraint) = pending.pop() {
+            if let Some(val) = constraint.expr.as_constant() {
+                if !constraint.relation.holds(val) {
+                    return None;
+                }
+                continue;
+            }
+
+            let Some((s, k, m)) = constraint.expr.as_linear() else {
+                // on a small domain, the values violating the constraint are excluded instead.
+                if let Some(results) = self.enumerate(&constraint.expr, constraint.relation) {
+                    let s = constraint.expr.symbols().next()?;
+                    let exclusions = results
+                        .into_iter()
+                        .filter(|&(_, holds)| !holds)
+                        .map(|(val, _)| {
+                            Some(Constraint {
+                                expr: Expr::symbol(s).checked_sub(&Expr::constant(val))?,
+                                relation: Relation::NonZero,
+                            })
+                        })
+                        .collect::<Option<Vec<_>>>();
+
+                    if let Some(exclusions) = exclusions {
+                        pending.extend(exclusions);
+                        continue;
+                    }
+                }
+
+                if !self.constraints.contains(&constraint) {
+                    self.constraints.push(constraint);
+                }
+                continue;
+            };
+
+            let domain = self.domains.get_mut(&s)?;
+            let was_single = domain.single().is_some();
+
+            match domain.restrict(k, m, constraint.relation) {
+                Some(true) => {}
+                Some(false) => return None,
+                // coefficients too large to narrow the domain with are kept as a constraint.
+                None => {
+                    if !self.constraints.contains(&constraint) {
+                        self.constraints.push(constraint);
+                    }
+                    continue;
+                }
+            }
+
+            if let (false, Some(val)) = (was_single, domain.single()) {
+                pinned.push((s, val));
+
+                // constraints mentioning the symbol may now fit into a domain, or be decided.
+                let (affected, rest) = std::mem::take(&mut self.constraints)
+                    .into_iter()
+                    .partition::<Vec<_>, _>(|c| c.expr.symbols().any(|other| other == s));
+
+                self.constraints = rest;
+                // a constraint whose coefficients would overflow keeps the symbol instead.
+                pending.extend(affected.into_iter().map(|c| Constraint {
+                    expr: c.expr.substitute(s, val).unwrap_or(c.expr),
+                    relation: c.relation,
+                }));
+            }
+        }
+
+        Some(pinned)
+    }
+}
+
+impl Display for Assumptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = vec![];
+
+        for (s, domain) in &self.domains {
+            match domain.single() {
+                Some(val) => parts.push(format!("{s} = {val}")),
+                None => {
+                    if domain.min != self.bounds.0 {
+                        parts.push(format!("{s} >= {}", domain.min));
+                    }
+                    if domain.max != self.bounds.1 {
+                        parts.push(format!("{s} <= {}", domain.max));
+                    }
+                    parts.extend(domain.excluded.iter().map(|val| format!("{s} != {val}")));
+                }
+            }
+        }
+
+        parts.extend(self.constraints.iter().map(ToString::to_string));
+
+        if parts.is_empty() {
+            write!(f, "always")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
+/// Why symbolic execution gave up on a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unresolved {
+    /// A `jnz` offset depends on a symbol and leads outside the program, or does not fit.
+    SymbolicOffset { index: usize },
+    /// The path ran too long, typically a loop whose trip count depends on a symbol.
+    StepLimit,
+    /// Too many paths were explored already.
+    PathLimit,
+    /// A [custom instruction](super::Instruction) has no symbolic semantics.
+    CustomInstruction { index: usize },
+    /// A coefficient of a symbolic value does not fit into an `i128`.
+    Overflow { index: usize },
+}
+
+/// What happens on one path through the program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The program exits with these register values, having sent `outputs`.
+    Exited {
+        registers: Vec<(char, Expr)>,
+        outputs: Vec<Expr>,
+    },
+    Fault(Halt),
+    Unresolved(Unresolved),
+}
+
+/// The outcome for all symbol values satisfying `assumptions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub assumptions: Assumptions,
+    pub outcome: Outcome,
+}
+
+/// The final registers of a program as a piecewise polynomial of the symbolic registers.
+///
+/// The cases partition the symbol values: every combination satisfies exactly one of them.
+/// Symbolic arithmetic assumes that no register overflows.
+#[derive(Debug, Clone)]
+pub struct ClosedForm {
+    names: Vec<char>,
+    cases: Vec<Case>,
+}
+
+impl ClosedForm {
+    pub fn cases(&self) -> &[Case] {
+        &self.cases
+    }
+
+    /// Whether every case was worked out, so [`evaluate`](Self::evaluate) never gives up.
+    pub fn is_complete(&self) -> bool {
+        self.cases
+            .iter()
+            .all(|case| !matches!(case.outcome, Outcome::Unresolved(_)))
+    }
+
+    /// The final registers for concrete symbol values, or `None` if the program does not exit
+    /// for them or their case is unresolved.
+    pub fn evaluate(&self, symbols: &[(char, i64)]) -> Option<RegisterFile> {
+        let value_of = |s: char| {
+            symbols
+                .iter()
+                .find(|(symbol, _)| *symbol == s)
+                .map(|&(_, val)| i128::from(val))
+        };
+
+        let case = self
+            .cases
+            .iter()
+            .find(|case| case.assumptions.holds(value_of))?;
+
+        let Outcome::Exited { registers, .. } = &case.outcome else {
+            return None;
+        };
+
+        let mut file = RegisterFile::new(&self.names);
+        for (register, expr) in registers {
+            let val = i64::try_from(expr.evaluate(value_of)?).ok()?;
+            file.write(*register, val);
+        }
+
+        Some(file)
+    }
+}
+
+impl Display for ClosedForm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for case in &self.cases {
+            write!(f, "{}: ", case.assumptions)?;
+
+            match &case.outcome {
+                Outcome::Exited { registers, .. } => {
+                    let registers = registers
+                        .iter()
+                        .map(|(r, expr)| format!("{r} = {expr}"))
+                        .collect::<Vec<_>>();
+                    writeln!(f, "{}", registers.join(", "))?;
+                }
+                Outcome::Fault(halt) => writeln!(f, "{halt}")?,
+                Outcome::Unresolved(reason) => writeln!(f, "unresolved ({reason:?})")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Control leaving the step function other than by moving on.
+enum Stop {
+    Outcome(Outcome),
+    /// The path contradicts its own assumptions and is dropped.
+    Infeasible,
+}
+
+impl Stop {
+    fn overflow(index: usize) -> Self {
+        Stop::Outcome(Outcome::Unresolved(Unresolved::Overflow { index }))
+    }
+}
+
+impl From<Halt> for Stop {
+    fn from(halt: Halt) -> Self {
+        Stop::Outcome(Outcome::Fault(halt))
+    }
+}
+
+/// A partially executed path, with registers indexed by slot.
+#[derive(Debug, Clone)]
+struct Path {
+    registers: Vec<Expr>,
+    instruction_pointer: usize,
+    program: Vec<PrototypeComputerInstruction>,
+    optimizer: Optimizer,
+    assumptions: Assumptions,
+    outputs: Vec<Expr>,
+    steps: u64,
+}
+
+impl Path {
+    fn value(&self, operand: Operand) -> Expr {
+        match operand {
+            Operand::Register(r) => self.registers[RegisterFile::slot(r)].clone(),
+            Operand::Immediate(val) => Expr::constant(i128::from(val)),
+        }
+    }
+
+    fn assume(&mut self, expr: Expr, relation: Relation) -> bool {
+        let Some(pinned) = self.assumptions.assume(expr, relation) else {
+            return false;
+        };
+
+        for (s, val) in pinned {
+            for expr in self.registers.iter_mut().chain(&mut self.outputs) {
+                if let Some(substituted) = expr.substitute(s, val) {
+                    *expr = substituted;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// Explores all paths through a program, forking whenever a condition depends on a symbol.
+struct SymbolicExecutor {
+    names: Vec<char>,
+    arithmetic: Arithmetic,
+    queue: Vec<Path>,
+    cases: Vec<Case>,
+    forks: usize,
+}
+
+impl SymbolicExecutor {
+    fn run(mut self) -> ClosedForm {
+        while let Some(mut path) = self.queue.pop() {
+            match self.explore(&mut path) {
+                Stop::Outcome(outcome) => self.cases.push(Case {
+                    assumptions: path.assumptions,
+                    outcome,
+                }),
+                Stop::Infeasible => {}
+            }
+        }
+
+        ClosedForm {
+            names: self.names,
+            cases: self.cases,
+        }
+    }
+
+    fn explore(&mut self, path: &mut Path) -> Stop {
+        loop {
+            if path.instruction_pointer >= path.program.len() {
+                let registers = self
+                    .names
+                    .iter()
+                    .map(|&r| (r, path.registers[RegisterFile::slot(r)].clone()))
+                    .collect();
+
+                return Stop::Outcome(Outcome::Exited {
+                    registers,
+                    outputs: std::mem::take(&mut path.outputs),
+                });
+            }
+
+            if path.steps >= MAX_STEPS {
+                return Stop::Outcome(Outcome::Unresolved(Unresolved::StepLimit));
+            }
+
+            path.steps += 1;
+
+            if let Err(stop) = self.step(path) {
+                return stop;
+            }
+        }
+    }
+
+    /// Decides whether `expr` satisfies the relation on this path. If that depends on the
+    /// symbols, the path continues assuming it does and a fork assuming the opposite is queued.
+    fn branch(&mut self, path: &mut Path, expr: Expr, relation: Relation) -> Result<bool, Stop> {
+        if let Some(holds) = path.assumptions.decide(&expr, relation) {
+            return Ok(holds);
+        }
+
+        let mut fork = path.clone();
+        if fork.assume(expr.clone(), relation.negate()) {
+            if self.forks == MAX_PATHS {
+                return Err(Stop::Outcome(Outcome::Unresolved(Unresolved::PathLimit)));
+            }
+
+            self.forks += 1;
+            self.queue.push(fork);
+        }
+
+        if path.assume(expr, relation) {
+            Ok(true)
+        } else {
+            Err(Stop::Infeasible)
+        }
+    }
+
+    fn store(&self, path: &mut Path, register: char, expr: Expr) -> Result<(), Stop> {
+        let index = path.instruction_pointer;
+
+        let expr = match expr.as_constant() {
+            Some(val) => {
+                let fitted = self
+                    .arithmetic
+                    .fit(val)
+                    .ok_or(Halt::Overflow(OverflowError {
+                        index,
+                        instruction: path.program[index],
+                    }))?;
+                Expr::constant(i128::from(fitted))
+            }
+            None => expr,
+        };
+
+        path.registers[RegisterFile::slot(register)] = expr;
+
+        Ok(())
+    }
+
+    /// Mirrors [`PrototypeComputer::step`] on symbolic registers.
+    fn step(&mut self, path: &mut Path) -> Result<(), Stop> {
+        let index = path.instruction_pointer;
+
+        if let Some(&block) = path.optimizer.block_at(index) {
+            if self.step_fused(path, block)? {
+                path.instruction_pointer += block.span();
+                return Ok(());
+            }
+        }
+
+        let instruction = path.program[index];
+
+        if instruction.y.is_some() != (instruction.opcode.arity() == 2) {
+            return Err(Halt::InvalidInstruction { index }.into());
+        }
+
+        match (instruction.opcode, instruction.x, instruction.y) {
+            (Opcode::Cpy, source, Some(Operand::Register(dest))) => {
+                self.store(path, dest, path.value(source))?;
+            }
+            (Opcode::Inc, Operand::Register(r), _) => {
+                let val = path
+                    .value(Operand::Register(r))
+                    .checked_add(&Expr::constant(1))
+                    .ok_or(Stop::overflow(index))?;
+                self.store(path, r, val)?;
+            }
+            (Opcode::Dec, Operand::Register(r), _) => {
+                let val = path
+                    .value(Operand::Register(r))
+                    .checked_sub(&Expr::constant(1))
+                    .ok_or(Stop::overflow(index))?;
+                self.store(path, r, val)?;
+            }
+            (Opcode::Jnz, source, Some(skip)) => {
+                let jumps = self.branch(path, path.value(source), Relation::NonZero)?;
+                if jumps {
+                    let offset =
+                        self.offset(path, skip)?
+                            .ok_or(Stop::Outcome(Outcome::Unresolved(
+                                Unresolved::SymbolicOffset { index },
+                            )))?;
+                    path.instruction_pointer =
+                        Self::target(index, offset).ok_or(Halt::InvalidJump { index, offset })?;
+                    return Ok(());
+                }
+            }
+            (Opcode::Tgl, target, _) => {
+                // a symbolic offset leading outside the program toggles nothing.
+                if let Some(target) = self
+                    .offset(path, target)?
+                    .and_then(|offset| Self::target(index, offset))
+                    .filter(|&t| t < path.program.len())
+                {
+                    let toggled = &mut path.program[target];
+                    toggled.opcode = toggled.opcode.toggled();
+                    path.optimizer.invalidate(&path.program, target);
+                }
+            }
+            (Opcode::Out, source, _) => path.outputs.push(path.value(source)),
+            (Opcode::Custom(_), ..) => {
+                return Err(Stop::Outcome(Outcome::Unresolved(
+                    Unresolved::CustomInstruction { index },
+                )));
+            }
+            _ => {}
+        }
+
+        path.instruction_pointer += 1;
+
+        Ok(())
+    }
+
+    /// Summarizes a fused loop, if its counters are positive on this path.
+    fn step_fused(&mut self, path: &mut Path, block: Fused) -> Result<bool, Stop> {
+        let overflow = Stop::overflow(path.instruction_pointer);
+
+        match block {
+            Fused::Add {
+                target,
+                counter,
+                delta,
+            } => {
+                let count = path.value(Operand::Register(counter));
+                if !self.branch(path, count.clone(), Relation::Positive)? {
+                    return Ok(false);
+                }
+
+                let sum = Expr::constant(i128::from(delta))
+                    .checked_mul(&count)
+                    .and_then(|product| path.value(Operand::Register(target)).checked_add(&product))
+                    .ok_or(overflow)?;
+                self.store(path, target, sum)?;
+                self.store(path, counter, Expr::constant(0))?;
+            }
+            Fused::Multiply {
+                target,
+                source,
+                inner,
+                outer,
+                delta,
+            } => {
+                let (factor, count) = (path.value(source), path.value(Operand::Register(outer)));
+                if !self.branch(path, factor.clone(), Relation::Positive)?
+                    || !self.branch(path, count.clone(), Relation::Positive)?
+                {
+                    return Ok(false);
+                }
+
+                let sum = Expr::constant(i128::from(delta))
+                    .checked_mul(&factor)
+                    .and_then(|product| product.checked_mul(&count))
+                    .and_then(|product| path.value(Operand::Register(target)).checked_add(&product))
+                    .ok_or(overflow)?;
+                self.store(path, target, sum)?;
+                self.store(path, inner, Expr::constant(0))?;
+                self.store(path, outer, Expr::constant(0))?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Resolves an offset operand. A symbolic offset forks once for every instruction it may
+    /// lead to; `None` is the remaining path, on which it leads outside the program.
+    fn offset(&mut self, path: &mut Path, operand: Operand) -> Result<Option<i64>, Stop> {
+        let index = path.instruction_pointer;
+        let expr = path.value(operand);
+
+        if let Some(offset) = expr.as_constant() {
+            let offset = i64::try_from(offset).map_err(|_| {
+                Stop::Outcome(Outcome::Unresolved(Unresolved::SymbolicOffset { index }))
+            })?;
+            return Ok(Some(offset));
+        }
+
+        for target in 0..path.program.len() {
+            let offset = target as i64 - index as i64;
+            let candidate = expr
+                .clone()
+                .checked_sub(&Expr::constant(i128::from(offset)))
+                .ok_or(Stop::overflow(index))?;
+            if self.branch(path, candidate, Relation::Zero)? {
+                return Ok(Some(offset));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn target(index: usize, offset: i64) -> Option<usize> {
+        index.checked_add_signed(isize::try_from(offset).ok()?)
+    }
+}
+
+impl PrototypeComputer {
+    /// Runs the loaded program symbolically from the current state, treating the given
+    /// registers as unknown values within their intervals and summarizing the loops the
+    /// optimizer recognizes.
+    ///
+    /// # Panics
+    /// Panics if a symbol is not a register of this computer.
+    pub fn closed_form(&self, symbols: &[(char, Interval)]) -> ClosedForm {
+        assert!(
+            symbols.iter().all(|&(s, _)| self.registers.contains(s)),
+            "symbols must be registers, got {symbols:?}"
+        );
+
+        let registers = (b'a'..=b'z')
+            .map(char::from)
+            .map(|r| {
+                if symbols.iter().any(|&(s, _)| s == r) {
+                    Expr::symbol(r)
+                } else {
+                    Expr::constant(i128::from(self.registers.read(r)))
+                }
+            })
+            .collect();
+
+        let path = Path {
+            registers,
+            instruction_pointer: self.instruction_pointer,
+            program: self.program.to_vec(),
+            optimizer: Optimizer::new(&self.program),
+            assumptions: Assumptions::new(symbols, self.arithmetic),
+            outputs: vec![],
+            steps: 0,
+        };
+
+        SymbolicExecutor {
+            names: self.registers.names().to_vec(),
+            arithmetic: self.arithmetic,
+            queue: vec![path],
+            cases: vec![],
+            forks: 0,
+        }
+        .run()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Overflow, Width};
+
+    fn closed_form(program: &[&str], symbols: &[(char, Interval)]) -> ClosedForm {
+        let mut computer = PrototypeComputer::new();
+        computer.load_program(program).unwrap();
+        computer.closed_form(symbols)
+    }
+
+    #[test]
+    fn summarizes_loops_symbolically() {
+        // b = a * a + 3, with the multiply loop fused.
+        let closed = closed_form(
+            &[
+                "cpy a d", "cpy a c", "inc b", "dec c", "jnz c -2", "dec d", "jnz d -5", "inc b",
+                "inc b", "inc b",
+            ],
+            &[('a', Interval::new(1, 100))],
+        );
+
+        assert!(closed.is_complete());
+        assert_eq!(closed.evaluate(&[('a', 5)]).unwrap().get('b'), Some(28));
+        assert_eq!(
+            closed.to_string(),
+            "a >= 1, a <= 100: a = a, b = a^2 + 3, c = 0, d = 0\n"
+        );
+    }
+
+    #[test]
+    fn leaves_overflowing_relations_undecided() {
+        let arithmetic = Arithmetic::new(Width::I64, Overflow::Checked);
+        let mut assumptions = Assumptions::new(&[('a', Interval::full(arithmetic))], arithmetic);
+
+        // 2^100 * a - 2^127
+        let expr = Expr::constant(1 << 100)
+            .checked_mul(&Expr::symbol('a'))
+            .and_then(|expr| expr.checked_add(&Expr::constant(i128::MIN)))
+            .unwrap();
+
+        assert_eq!(assumptions.decide(&expr, Relation::Positive), None);
+        assert_eq!(
+            assumptions.assume(expr.clone(), Relation::Positive),
+            Some(vec![])
+        );
+        assert_eq!(assumptions.constraints().len(), 1);
+        assert_eq!(assumptions.decide(&expr, Relation::Positive), Some(true));
+    }
+
+    #[test]
+    fn splits_on_symbolic_branches() {
+        let full = Interval::full(Arithmetic::default());
+        let closed = closed_form(&["jnz c 2", "cpy 1 a", "inc a"], &[('c', full)]);
+
+        assert_eq!(closed.cases().len(), 2);
+        assert_eq!(closed.evaluate(&[('c', 0)]).unwrap().get('a'), Some(2));
+        assert_eq!(closed.evaluate(&[('c', -4)]).unwrap().get('a'), Some(1));
+    }
+
+    #[test]
+    fn gives_up_on_symbolic_trip_counts() {
+        let closed = closed_form(
+            &["dec a", "inc b", "inc b", "jnz a -3"],
+            &[('a', Interval::new(1, 1000))],
+        );
+
+        assert!(!closed.is_complete());
+        assert_eq!(closed.evaluate(&[('a', 3)]).unwrap().get('b'), Some(6));
+        assert_eq!(closed.evaluate(&[('a', 1000)]), None);
+    }
+
+    #[test]
+    fn forks_on_symbolic_offsets() {
+        // `a = 0` toggles the `tgl` itself, which has no further effect.
+        let closed = closed_form(&["tgl a", "inc b", "inc c"], &[('a', Interval::new(0, 5))]);
+
+        assert!(closed.is_complete());
+        assert_eq!(closed.cases().len(), 4);
+
+        let toggled = |a| {
+            let registers = closed.evaluate(&[('a', a)]).unwrap();
+            (registers.get('b'), registers.get('c'))
+        };
+
+        assert_eq!(toggled(0), (Some(1), Some(1)));
+        assert_eq!(toggled(1), (Some(-1), Some(1)));
+        assert_eq!(toggled(2), (Some(1), Some(-1)));
+        assert_eq!(toggled(5), (Some(1), Some(1)));
+    }
+}
@@ -0,0 +1,262 @@
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::{PrototypeComputerInstruction, RegisterFile, TRACE_ENV_VAR};
+
+/// Records an execution trace and counts how often each program line ran.
+///
+/// Each executed step becomes one line: `<ip> <opcode>[ >output][ reg=value...]`, listing only
+/// the registers that changed. Fused optimizer blocks are reported as `add`/`mul`.
+pub struct Tracer {
+    writer: Option<(PathBuf, BufWriter<File>)>,
+    hits: Vec<u64>,
+}
+
+/// Line hits of the computers that traced to a file, merged per file and program until
+/// [`write_hot_spots`] reports them.
+static SESSION: Mutex<Vec<ProgramHits>> = Mutex::new(Vec::new());
+
+struct ProgramHits {
+    path: PathBuf,
+    program: Vec<String>,
+    hits: Vec<u64>,
+}
+
+impl std::fmt::Debug for Tracer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tracer")
+            .field("writes_trace", &self.writer.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Tracer {
+    /// A tracer that only counts line hits.
+    pub fn profile_only() -> Self {
+        Self {
+            writer: None,
+            hits: Vec::new(),
+        }
+    }
+
+    /// A tracer that appends its trace to `path`.
+    pub fn to_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        Ok(Self {
+            writer: Some((path.as_ref().to_path_buf(), BufWriter::new(file))),
+            hits: Vec::new(),
+        })
+    }
+
+    /// A tracer writing to the file named by [`TRACE_ENV_VAR`], if it is set.
+    pub fn from_env() -> Option<Self> {
+        let path = std::env::var(TRACE_ENV_VAR).ok()?;
+
+        match Self::to_file(&path) {
+            Ok(tracer) => Some(tracer),
+            Err(e) => {
+                eprintln!("Failed to open trace file {path}: {e}");
+                None
+            }
+        }
+    }
+
+    /// How many times each instruction index was executed.
+    pub fn hits(&self) -> &[u64] {
+        &self.hits
+    }
+
+    pub(super) fn record(
+        &mut self,
+        index: usize,
+        label: &str,
+        output: Option<i64>,
+        before: &RegisterFile,
+        after: &RegisterFile,
+    ) {
+        if self.hits.len() <= index {
+            self.hits.resize(index + 1, 0);
+        }
+        self.hits[index] += 1;
+
+        let Some((_, writer)) = &mut self.writer else {
+            return;
+        };
+
+        let mut line = format!("{index} {label}");
+
+        if let Some(val) = output {
+            let _ = write!(line, " >{val}");
+        }
+
+        for ((register, new), (_, old)) in after.iter().zip(before.iter()) {
+            if new != old {
+                let _ = write!(line, " {register}={new}");
+            }
+        }
+
+        if let Err(e) = writeln!(writer, "{line}") {
+            eprintln!("Failed to write trace, disabling it: {e}");
+            self.writer = None;
+        }
+    }
+
+    /// Program lines ordered by how often they ran, most frequent first.
+    pub fn hot_spots(&self, program: &[PrototypeComputerInstruction]) -> String {
+        let program = program.iter().map(ToString::to_string).collect::<Vec<_>>();
+        format_hot_spots(&self.hits, &program)
+    }
+
+    /// Flushes the trace and adds the line hits to the report of its file.
+    pub(super) fn finish(&mut self, program: &[PrototypeComputerInstruction]) {
+        let Some((path, writer)) = &mut self.writer else {
+            return;
+        };
+
+        let _ = writer.flush();
+
+        let program = program.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let mut session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+
+        match session
+            .iter_mut()
+            .find(|entry| entry.path == *path && entry.program == program)
+        {
+            Some(entry) => {
+                if entry.hits.len() < self.hits.len() {
+                    entry.hits.resize(self.hits.len(), 0);
+                }
+                for (total, hits) in entry.hits.iter_mut().zip(&self.hits) {
+                    *total += hits;
+                }
+            }
+            None => session.push(ProgramHits {
+                path: path.clone(),
+                program,
+                hits: std::mem::take(&mut self.hits),
+            }),
+        }
+    }
+}
+
+/// Appends one hot-spot report per program traced to `path` since the last report, as
+/// `#`-prefixed lines. Computers running the same program share a report.
+pub fn write_hot_spots(path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref();
+
+    let reports = {
+        let mut session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+        let (reports, rest) = std::mem::take(&mut *session)
+            .into_iter()
+            .partition::<Vec<_>, _>(|entry| entry.path == path);
+        *session = rest;
+        reports
+    };
+
+    if reports.is_empty() {
+        return Ok(());
+    }
+
+    let mut writer = BufWriter::new(OpenOptions::new().create(true).append(true).open(path)?);
+
+    for entry in reports {
+        writeln!(writer, "# hot spots")?;
+        for line in format_hot_spots(&entry.hits, &entry.program).lines() {
+            writeln!(writer, "# {line}")?;
+        }
+    }
+
+    writer.flush()
+}
+
+fn format_hot_spots(hits: &[u64], program: &[String]) -> String {
+    let mut lines = hits
+        .iter()
+        .enumerate()
+        .filter(|(_, &hits)| hits > 0)
+        .collect::<Vec<_>>();
+
+    lines.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(&b.0)));
+
+    lines
+        .iter()
+        .map(|(index, hits)| {
+            let instruction = program.get(*index).map(String::as_str);
+            format!("{hits:>12} {index:>3}: {}", instruction.unwrap_or_default())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PrototypeComputer;
+
+    #[test]
+    fn counts_line_hits() {
+        let mut computer = PrototypeComputer::new();
+        computer.set_tracer(Some(Tracer::profile_only()));
+        computer
+            .run_program(&["cpy 3 a", "dec a", "jnz a -1"])
+            .unwrap();
+
+        let tracer = computer.tracer().unwrap();
+        assert_eq!(tracer.hits(), &[1, 3, 3]);
+        assert_eq!(
+            tracer.hot_spots(computer.program()).lines().next(),
+            Some("           3   1: dec a")
+        );
+    }
+
+    #[test]
+    fn writes_register_deltas() {
+        let path = std::env::temp_dir().join(format!("assembunny-{}.trace", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut computer = PrototypeComputer::new();
+        computer.set_tracer(Some(Tracer::to_file(&path).unwrap()));
+        computer
+            .run_program(&["cpy 2 a", "out a", "jnz 0 0"])
+            .unwrap();
+        drop(computer);
+        write_hot_spots(&path).unwrap();
+
+        let trace = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert!(trace.starts_with("0 cpy a=2\n1 out >2\n2 jnz\n# hot spots\n"));
+    }
+
+    #[test]
+    fn reports_each_program_once() {
+        let path =
+            std::env::temp_dir().join(format!("assembunny-{}-report.trace", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        for _ in 0..3 {
+            let mut computer = PrototypeComputer::new();
+            computer.set_tracer(Some(Tracer::to_file(&path).unwrap()));
+            computer
+                .run_program(&["cpy 2 a", "dec a", "jnz a -1"])
+                .unwrap();
+        }
+
+        // nothing is reported until the session ends.
+        let trace = std::fs::read_to_string(&path).unwrap();
+        assert!(!trace.contains("# hot spots"));
+
+        write_hot_spots(&path).unwrap();
+        write_hot_spots(&path).unwrap();
+
+        let trace = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(trace.matches("# hot spots").count(), 1);
+        assert!(trace.contains("#            6   1: dec a\n"));
+    }
+}
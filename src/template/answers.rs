@@ -0,0 +1,126 @@
+/// Module that stores accepted answers, so that `cargo verify` can check solutions against them.
+use std::{fmt::Display, fs, io, path::PathBuf};
+
+use crate::template::report::{PartReport, Status};
+use crate::template::Day;
+
+static ANSWERS_DIR_PATH: &str = "./data/answers";
+
+/// Path of the answer to a part, e.g. `data/answers/01-2.txt`.
+pub fn get_path(day: Day, part: u8) -> PathBuf {
+    PathBuf::from(ANSWERS_DIR_PATH).join(format!("{day}-{part}.txt"))
+}
+
+/// The stored answer to a part, if any.
+pub fn read(day: Day, part: u8) -> Option<String> {
+    let answer = fs::read_to_string(get_path(day, part)).ok()?;
+
+    // tolerate the trailing newline editors add to hand-written answers.
+    Some(answer.strip_suffix('\n').unwrap_or(&answer).to_string())
+}
+
+pub fn store(day: Day, part: u8, answer: &str) -> io::Result<()> {
+    fs::create_dir_all(ANSWERS_DIR_PATH)?;
+    fs::write(get_path(day, part), format!("{answer}\n"))
+}
+
+/// How a part's result compares to its stored answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        /// The answer given, or why there was none.
+        actual: String,
+    },
+    /// Solved, but there is no stored answer to compare against.
+    Unverified,
+    /// Neither solved nor answered.
+    Skipped,
+}
+
+impl Verdict {
+    pub fn check(expected: Option<&str>, report: Option<&PartReport>) -> Self {
+        let actual = match report {
+            None => Err("no result".to_string()),
+            Some(report) => match (&report.answer, report.status) {
+                (Some(answer), _) => Ok(answer.as_str()),
+                (None, Status::Panicked) => Err(format!(
+                    "panicked: {}",
+                    report.error.as_deref().unwrap_or("unknown panic")
+                )),
+                (None, status) => Err(status.to_string()),
+            },
+        };
+
+        match (expected, actual) {
+            (Some(expected), Ok(actual)) if expected == actual => Verdict::Pass,
+            (Some(expected), actual) => Verdict::Fail {
+                expected: expected.into(),
+                actual: actual.map_or_else(|reason| reason, |answer| format!("`{answer}`")),
+            },
+            (None, Ok(_)) => Verdict::Unverified,
+            (None, Err(_)) => Verdict::Skipped,
+        }
+    }
+}
+
+/// A cell of the pass/fail matrix.
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => f.write_str("✔"),
+            Verdict::Fail { .. } => f.write_str("✘"),
+            Verdict::Unverified => f.write_str("?"),
+            Verdict::Skipped => f.write_str("-"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Verdict;
+    use crate::day;
+    use crate::template::report::{PartReport, Status};
+
+    fn report(status: Status, answer: Option<&str>) -> PartReport {
+        PartReport {
+            day: day!(1),
+            part: 1,
+            status,
+            answer: answer.map(Into::into),
+            nanos: 0.0,
+            samples: 1,
+            error: (status == Status::Panicked).then(|| "oops".into()),
+        }
+    }
+
+    #[test]
+    fn compares_answers() {
+        let solved = report(Status::Solved, Some("42"));
+
+        assert_eq!(Verdict::check(Some("42"), Some(&solved)), Verdict::Pass);
+        assert_eq!(
+            Verdict::check(Some("41"), Some(&solved)),
+            Verdict::Fail {
+                expected: "41".into(),
+                actual: "`42`".into()
+            }
+        );
+        assert_eq!(Verdict::check(None, Some(&solved)), Verdict::Unverified);
+    }
+
+    #[test]
+    fn fails_parts_without_answers() {
+        for (report, reason) in [
+            (Some(report(Status::Unsolved, None)), "unsolved"),
+            (Some(report(Status::Panicked, None)), "panicked: oops"),
+            (None, "no result"),
+        ] {
+            let verdict = Verdict::check(Some("42"), report.as_ref());
+            assert!(matches!(verdict, Verdict::Fail { actual, .. } if actual == reason));
+
+            assert_eq!(Verdict::check(None, report.as_ref()), Verdict::Skipped);
+        }
+    }
+}
@@ -1,5 +1,18 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::all_days;
+use crate::template::report::Format;
+use crate::template::run_multi::{report_multi, run_multi, run_multi_parallel};
+use crate::Backend;
+
+pub fn handle(is_release: bool, format: Option<Format>, jobs: usize, timeout: Option<Duration>) {
+    let days = all_days().collect();
+
+    match format {
+        Some(format) => report_multi(&days, is_release, format, jobs, timeout),
+        None if jobs > 1 => run_multi_parallel(&days, is_release, jobs, timeout),
+        None => {
+            run_multi(&days, is_release, false, Backend::default(), timeout);
+        }
+    }
 }
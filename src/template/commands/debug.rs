@@ -0,0 +1,171 @@
+use std::io::{stdin, stdout, Write};
+use std::process;
+
+use crate::template::{read_file, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::{Debugger, PrototypeComputer, StopReason};
+
+const HELP: &str = "\
+commands:
+  s, step [n]        execute n instructions (default 1)
+  c, continue        run until a breakpoint, watchpoint or the end of the program
+  b, break <index>   toggle a breakpoint on an instruction index
+  w, watch <reg>     toggle a watchpoint on a register
+  r, regs            print the register file
+  p, print           print the current instruction
+  l, list            print the program as it currently is
+  set <reg> <value>  change a register
+  o, out             print the values sent via `out` so far
+  q, quit            leave the debugger";
+
+pub fn handle(day: Day) {
+    let input = read_file("inputs", day);
+    let mut computer = PrototypeComputer::new();
+
+    if let Err(e) = computer.load_program(&input.lines().collect::<Vec<_>>()) {
+        eprintln!("Failed to parse program: {e}");
+        process::exit(1);
+    }
+
+    let mut debugger = Debugger::new(computer);
+
+    println!("🎄 Debugging day {day}. Type `help` for a list of commands.");
+    print_instruction(&debugger);
+
+    loop {
+        print!("{ANSI_ITALIC}(dbg){ANSI_RESET} ");
+        let _ = stdout().flush();
+
+        let mut line = String::new();
+        if stdin().read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+
+        let mut words = line.split_whitespace();
+
+        match (words.next(), words.next(), words.next()) {
+            (None, ..) => {}
+            (Some("s" | "step"), count, _) => {
+                let count = count.and_then(|c| c.parse().ok()).unwrap_or(1);
+                let mut reason = StopReason::Stepped;
+                for _ in 0..count {
+                    reason = debugger.step();
+                    if reason != StopReason::Stepped {
+                        break;
+                    }
+                }
+                report(&debugger, reason);
+            }
+            (Some("c" | "continue"), ..) => {
+                let reason = debugger.resume();
+                report(&debugger, reason);
+            }
+            (Some("b" | "break"), Some(index), _) => match index.parse() {
+                Ok(index) => {
+                    let state = if debugger.toggle_breakpoint(index) {
+                        "set"
+                    } else {
+                        "removed"
+                    };
+                    println!("Breakpoint at {index} {state}.");
+                }
+                Err(_) => eprintln!("Expected an instruction index, got `{index}`."),
+            },
+            (Some("w" | "watch"), Some(register), _) => match parse_register(&debugger, register) {
+                Some(register) => {
+                    let state = if debugger.toggle_watchpoint(register) {
+                        "set"
+                    } else {
+                        "removed"
+                    };
+                    println!("Watchpoint on {register} {state}.");
+                }
+                None => eprintln!("Unknown register `{register}`."),
+            },
+            (Some("set"), Some(register), Some(val)) => {
+                match (parse_register(&debugger, register), val.parse()) {
+                    (Some(register), Ok(val)) => {
+                        if let Err(e) = debugger.computer.set_register(register, val) {
+                            eprintln!("{e}");
+                        }
+                    }
+                    _ => eprintln!("Usage: set <reg> <value>"),
+                }
+            }
+            (Some("r" | "regs"), ..) => print_registers(&debugger),
+            (Some("p" | "print"), ..) => print_instruction(&debugger),
+            (Some("l" | "list"), ..) => print_program(&debugger),
+            (Some("o" | "out"), ..) => println!("{:?}", debugger.outputs()),
+            (Some("q" | "quit"), ..) => break,
+            (Some("h" | "help"), ..) => println!("{HELP}"),
+            (Some(command), ..) => eprintln!("Unknown command `{command}`. Type `help`."),
+        }
+    }
+}
+
+fn parse_register(debugger: &Debugger, s: &str) -> Option<char> {
+    let register = s.chars().next()?;
+    (s.len() == 1 && debugger.computer.get_register(register).is_some()).then_some(register)
+}
+
+fn report(debugger: &Debugger, reason: StopReason) {
+    match reason {
+        StopReason::Stepped => {}
+        StopReason::Breakpoint(index) => println!("Hit breakpoint at {index}."),
+        StopReason::Watchpoint { register, old, new } => {
+            println!("Register {register} changed: {old} -> {new}.");
+        }
+        StopReason::Fault(halt) => println!("Stopped: {halt}."),
+        StopReason::Halted => {
+            println!("Program halted.");
+            print_registers(debugger);
+            return;
+        }
+    }
+
+    print_instruction(debugger);
+}
+
+fn print_registers(debugger: &Debugger) {
+    let registers = debugger
+        .computer
+        .registers()
+        .iter()
+        .map(|(r, v)| format!("{r}={v}"))
+        .collect::<Vec<_>>();
+
+    println!("{}", registers.join(" "));
+}
+
+fn format_line(debugger: &Debugger, index: usize) -> String {
+    let instruction = debugger.computer.program()[index];
+
+    let marker = if index == debugger.computer.instruction_pointer() {
+        '>'
+    } else if debugger.breakpoints().any(|&b| b == index) {
+        '*'
+    } else {
+        ' '
+    };
+
+    let toggled = debugger
+        .toggled_from(index)
+        .map(|original| format!("  {ANSI_ITALIC}(toggled from `{original}`){ANSI_RESET}"))
+        .unwrap_or_default();
+
+    format!("{marker} {index:>3}: {instruction}{toggled}")
+}
+
+fn print_instruction(debugger: &Debugger) {
+    if debugger.computer.is_halted() {
+        println!("<end of program>");
+    } else {
+        let line = format_line(debugger, debugger.computer.instruction_pointer());
+        println!("{ANSI_BOLD}{line}{ANSI_RESET}");
+    }
+}
+
+fn print_program(debugger: &Debugger) {
+    for index in 0..debugger.computer.program().len() {
+        println!("{}", format_line(debugger, index));
+    }
+}
@@ -0,0 +1,42 @@
+use std::process;
+
+use crate::template::{read_file, Day};
+use crate::{Interval, PrototypeComputer, ToggleAnalysis};
+
+pub fn handle(day: Day, dot: bool) {
+    let input = read_file("inputs", day);
+
+    let computer = PrototypeComputer::new();
+    let disassembly = match computer.disassemble(&input.lines().collect::<Vec<_>>()) {
+        Ok(disassembly) => disassembly,
+        Err(e) => {
+            eprintln!("Failed to parse program: {e}");
+            process::exit(1);
+        }
+    };
+
+    if dot {
+        print!("{}", disassembly.to_dot());
+    } else {
+        print!("{disassembly}");
+
+        // solutions may set any register before running, so none of them is known.
+        let unknown = computer
+            .registers()
+            .names()
+            .iter()
+            .map(|&r| (r, Interval::full(computer.arithmetic())))
+            .collect::<Vec<_>>();
+
+        let analysis = ToggleAnalysis::new(disassembly.program(), computer.arithmetic(), &unknown);
+        let targets = analysis
+            .targets()
+            .map(|t| t.to_string())
+            .collect::<Vec<_>>();
+
+        println!("; self-modification: {:?}", analysis.classification());
+        if !targets.is_empty() {
+            println!("; may be toggled: {}", targets.join(", "));
+        }
+    }
+}
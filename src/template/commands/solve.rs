@@ -1,10 +1,25 @@
+use std::fs;
 use std::process::{Command, Stdio};
 
+use crate::template::report::Format;
 use crate::template::Day;
+use crate::TRACE_ENV_VAR;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    trace: bool,
+    submit_part: Option<u8>,
+    format: Option<Format>,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
+    if format.is_some() {
+        // keep cargo's progress output out of the way of scripts.
+        cmd_args.push("--quiet".to_string());
+    }
+
     if dhat {
         cmd_args.extend([
             "--profile".to_string(),
@@ -16,6 +31,16 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    let trace_path = format!("data/traces/{day}.trace");
+
+    if trace {
+        cmd_args.extend(["--features".to_string(), "trace".to_string()]);
+
+        // the tracer appends, so start from an empty file.
+        let _ = fs::create_dir_all("data/traces");
+        let _ = fs::remove_file(&trace_path);
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,12 +48,23 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+    if let Some(format) = format {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(&cmd_args)
         .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .stderr(Stdio::inherit());
+
+    if trace {
+        cmd.env(TRACE_ENV_VAR, &trace_path);
+    }
+
+    cmd.spawn().unwrap().wait().unwrap();
 
-    cmd.wait().unwrap();
+    if trace {
+        println!("🎄 Wrote execution trace to \"{trace_path}\".");
+    }
 }
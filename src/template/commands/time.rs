@@ -1,10 +1,22 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
+use crate::template::history::{self, Record};
+use crate::template::regression::{self, DEFAULT_THRESHOLD};
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, Day, ANSI_BOLD, ANSI_RESET};
+use crate::Backend;
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    interpreter: bool,
+    threshold: Option<f64>,
+    timeout: Option<Duration>,
+) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,9 +33,53 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let backend = if interpreter {
+        Backend::Interpreter
+    } else {
+        Backend::Compiled
+    };
+    let timings = run_multi(&days_to_run, true, true, backend, timeout).unwrap();
 
-    if store {
+    // runs of different backends are not comparable, so their histories are kept apart.
+    let profile = if interpreter {
+        "release-interpreter"
+    } else {
+        "release"
+    };
+    let record = Record::new(profile, &timings);
+    let mut history = history::read();
+    if let Err(e) = history::append(std::slice::from_ref(&record)) {
+        eprintln!("Failed to record timing history: {e}");
+    }
+    history.push(record);
+    print_trend(&history, &timings, profile);
+
+    // the stored baseline was measured with the compiled backend.
+    let regressions = if interpreter {
+        0
+    } else {
+        compare_to_baseline(
+            &stored_timings,
+            &timings,
+            threshold.unwrap_or(DEFAULT_THRESHOLD),
+        )
+    };
+
+    if regressions > 0 {
+        eprintln!(
+            "\n{regressions} part(s) regressed. Not storing benchmarks; \
+            pass a larger `--threshold` to accept the slowdown."
+        );
+        process::exit(1);
+    }
+
+    if !store {
+        return;
+    }
+
+    if interpreter {
+        eprintln!("Not storing benchmarks measured with the interpreter backend.");
+    } else {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
@@ -38,3 +94,56 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Prints how each part changed relative to the baseline and counts the regressions.
+fn compare_to_baseline(baseline: &Timings, timings: &Timings, threshold: f64) -> usize {
+    let changes = regression::compare(baseline, timings);
+    if changes.is_empty() {
+        return 0;
+    }
+
+    println!("\n{ANSI_BOLD}Compared to baseline:{ANSI_RESET}");
+
+    let mut regressions = 0;
+    for change in changes {
+        if change.is_regression(threshold) {
+            regressions += 1;
+            println!("{change} ⚠ regression beyond {threshold}%");
+        } else {
+            println!("{change}");
+        }
+    }
+
+    regressions
+}
+
+/// Prints the recent runs of every part that has been measured before.
+fn print_trend(history: &[Record], timings: &Timings, profile: &str) {
+    let mut lines = vec![];
+
+    for timing in &timings.data {
+        for part in [1, 2] {
+            let runs = history::trend(history, timing.day, part, profile);
+            if runs.len() < 2 {
+                continue;
+            }
+
+            let runs = runs
+                .iter()
+                .map(|(record, nanos)| {
+                    format!("{:.3}ms ({})", nanos / 1_000_000_f64, record.label())
+                })
+                .collect::<Vec<_>>();
+            lines.push(format!(
+                "Day {} part {part}: {}",
+                timing.day,
+                runs.join(" → ")
+            ));
+        }
+    }
+
+    if !lines.is_empty() {
+        println!("\n{ANSI_BOLD}Trend:{ANSI_RESET}");
+        println!("{}", lines.join("\n"));
+    }
+}
@@ -0,0 +1,76 @@
+use std::path::Path;
+use std::process;
+
+use crate::template::answers::{self, Verdict};
+use crate::template::report::{Format, PartReport};
+use crate::template::run_multi::{build_once, child_commands::run_solution, get_path_for_bin};
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_RESET};
+use crate::Backend;
+
+pub fn handle(is_release: bool) {
+    let mut rows: Vec<(Day, [Verdict; 2])> = vec![];
+
+    let binaries = build_once(is_release);
+
+    for day in all_days().filter(|day| Path::new(&get_path_for_bin(*day)).exists()) {
+        println!("Verifying day {day}...");
+
+        // a day that did not build or run has no results, which fails any stored answer.
+        let lines = match binaries.get(&day) {
+            Some(binary) => {
+                match run_solution(binary, false, Some(Format::Json), Backend::default(), None) {
+                    Ok(run) => run.lines,
+                    Err(e) => {
+                        eprintln!("Failed to run day {day}: {e:?}");
+                        vec![]
+                    }
+                }
+            }
+            None => vec![],
+        };
+
+        let reports = lines
+            .iter()
+            .filter_map(|line| PartReport::from_json(line).ok())
+            .collect::<Vec<_>>();
+
+        let verdicts = [1, 2].map(|part| {
+            let report = reports.iter().find(|report| report.part == part);
+            Verdict::check(answers::read(day, part).as_deref(), report)
+        });
+
+        rows.push((day, verdicts));
+    }
+
+    println!();
+    println!("| Day | Part 1 | Part 2 |");
+    println!("| :---: | :---: | :---: |");
+    for (day, [part_1, part_2]) in &rows {
+        println!("| {day} | {part_1} | {part_2} |");
+    }
+
+    let failures = rows
+        .iter()
+        .flat_map(|(day, verdicts)| (1..).zip(verdicts).map(move |(part, v)| (day, part, v)))
+        .filter_map(|(day, part, verdict)| match verdict {
+            Verdict::Fail { expected, actual } => Some((day, part, expected, actual)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if failures.is_empty() {
+        println!("\n{ANSI_BOLD}All stored answers match.{ANSI_RESET}");
+        return;
+    }
+
+    println!();
+    for (day, part, expected, actual) in &failures {
+        println!("Day {day} part {part}: expected `{expected}`, got {actual}.");
+    }
+
+    eprintln!(
+        "\n{} part(s) do not match their stored answer.",
+        failures.len()
+    );
+    process::exit(1);
+}
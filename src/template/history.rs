@@ -0,0 +1,409 @@
+/// Module that keeps an append-only history of benchmark runs next to the stored timings.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, ErrorKind, Write},
+    path::Path,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::timings::{Timings, TIMINGS_FILE_PATH};
+use crate::template::Day;
+
+/// One JSON [`Record`] per line. Runs are only ever appended.
+static HISTORY_FILE_PATH: &str = "./data/timings-history.jsonl";
+
+/// Profile of timings stored before the history existed; `cargo time` always builds in release.
+static MIGRATED_PROFILE: &str = "release";
+
+/// Runs shown per part by [`trend`].
+pub const TREND_LENGTH: usize = 5;
+
+/// Mean time of one part in a recorded run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub day: Day,
+    pub part: u8,
+    pub nanos: f64,
+}
+
+/// A single benchmark run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    /// Seconds since the Unix epoch. Unknown for timings migrated from before the history.
+    pub timestamp: Option<u64>,
+    /// Commit the run was measured at, if it could be read from `.git`.
+    pub commit: Option<String>,
+    /// Build profile and backend the solutions ran with, e.g. `release`.
+    pub profile: String,
+    pub parts: Vec<PartRecord>,
+}
+
+impl Record {
+    /// Records the parts of `timings`, measured now at the checked out commit.
+    pub fn new(profile: &str, timings: &Timings) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .map(|d| d.as_secs());
+
+        Self {
+            timestamp,
+            commit: current_commit(Path::new(".git")),
+            ..Self::from_timings(profile, timings)
+        }
+    }
+
+    /// Records the parts of `timings` without a time or commit.
+    pub fn from_timings(profile: &str, timings: &Timings) -> Self {
+        let parts = timings
+            .data
+            .iter()
+            .flat_map(|timing| {
+                [1, 2].into_iter().filter_map(|part| {
+                    #[allow(clippy::cast_precision_loss)]
+                    Some(PartRecord {
+                        day: timing.day,
+                        part,
+                        nanos: timing.part(part)?.nanos as f64,
+                    })
+                })
+            })
+            .collect();
+
+        Self {
+            timestamp: None,
+            commit: None,
+            profile: profile.into(),
+            parts,
+        }
+    }
+
+    pub fn nanos(&self, day: Day, part: u8) -> Option<f64> {
+        self.parts
+            .iter()
+            .find(|p| p.day == day && p.part == part)
+            .map(|p| p.nanos)
+    }
+
+    /// Abbreviated commit hash, or `?` if unknown.
+    pub fn label(&self) -> &str {
+        self.commit
+            .as_deref()
+            .map_or("?", |commit| &commit[..commit.len().min(7)])
+    }
+}
+
+/// Reads the hash of the checked out commit from a `.git` directory.
+pub fn current_commit(git_dir: &Path) -> Option<String> {
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+
+    // a detached head holds the hash itself.
+    let Some(reference) = head.trim().strip_prefix("ref: ") else {
+        return Some(head.trim().into());
+    };
+
+    if let Ok(hash) = fs::read_to_string(git_dir.join(reference)) {
+        return Some(hash.trim().into());
+    }
+
+    // references of older commits may have been packed by `git gc`.
+    fs::read_to_string(git_dir.join("packed-refs"))
+        .ok()?
+        .lines()
+        .find_map(|line| {
+            let (hash, name) = line.split_once(' ')?;
+            (name == reference).then(|| hash.into())
+        })
+}
+
+/// Reads every recorded run, oldest first. Without a history file, it is started from the
+/// stored timings.
+pub fn read() -> Vec<Record> {
+    match fs::read_to_string(HISTORY_FILE_PATH) {
+        Ok(s) => parse(&s),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            let records = fs::read_to_string(TIMINGS_FILE_PATH)
+                .map(|s| migrate(&s))
+                .unwrap_or_default();
+
+            if let Err(e) = append(&records) {
+                eprintln!("Failed to migrate timing history: {e}");
+            }
+
+            records
+        }
+        Err(e) => {
+            eprintln!("Failed to read timing history: {e}");
+            vec![]
+        }
+    }
+}
+
+/// Appends runs to the history file, creating it if needed.
+pub fn append(records: &[Record]) -> io::Result<()> {
+    if records.is_empty() {
+        return Ok(());
+    }
+
+    let lines = records
+        .iter()
+        .map(|record| {
+            // stringifying values built from numbers and strings cannot fail.
+            JsonValue::from(record).stringify().unwrap() + "\n"
+        })
+        .collect::<String>();
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE_PATH)?
+        .write_all(lines.as_bytes())
+}
+
+/// Parses the lines of a history file. A run interrupted while appending leaves a truncated
+/// last line, which is skipped.
+fn parse(s: &str) -> Vec<Record> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(index, line)| {
+            let record = JsonValue::from_str(line)
+                .map_err(|_| "Expected history line to be valid JSON.".to_string())
+                .and_then(|json| Record::try_from(&json));
+
+            match record {
+                Ok(record) => Some(record),
+                Err(e) => {
+                    eprintln!("Skipping line {} of the timing history: {e}", index + 1);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// The run already known from a stored timings file, as a single run without time or commit.
+fn migrate(timings_json: &str) -> Vec<Record> {
+    match Timings::try_from(timings_json.to_string()) {
+        Ok(timings) if !timings.data.is_empty() => {
+            vec![Record::from_timings(MIGRATED_PROFILE, &timings)]
+        }
+        _ => vec![],
+    }
+}
+
+/// The last [`TREND_LENGTH`] runs of a part with the given profile, oldest first.
+pub fn trend<'a>(
+    history: &'a [Record],
+    day: Day,
+    part: u8,
+    profile: &str,
+) -> Vec<(&'a Record, f64)> {
+    let mut runs = history
+        .iter()
+        .rev()
+        .filter(|record| record.profile == profile)
+        .filter_map(|record| Some((record, record.nanos(day, part)?)))
+        .take(TREND_LENGTH)
+        .collect::<Vec<_>>();
+
+    runs.reverse();
+    runs
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Record> for JsonValue {
+    fn from(value: &Record) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let timestamp = value
+            .timestamp
+            .map_or(JsonValue::Null, |t| JsonValue::Number(t as f64));
+
+        let parts = value
+            .parts
+            .iter()
+            .map(|p| {
+                JsonValue::Object(HashMap::from([
+                    ("day".into(), JsonValue::String(p.day.to_string())),
+                    ("part".into(), JsonValue::Number(f64::from(p.part))),
+                    ("nanos".into(), JsonValue::Number(p.nanos)),
+                ]))
+            })
+            .collect();
+
+        let map = HashMap::from([
+            ("timestamp".into(), timestamp),
+            (
+                "commit".into(),
+                value
+                    .commit
+                    .clone()
+                    .map_or(JsonValue::Null, JsonValue::String),
+            ),
+            ("profile".into(), JsonValue::String(value.profile.clone())),
+            ("parts".into(), JsonValue::Array(parts)),
+        ]);
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history part to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected history part.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|&part| part as u8)
+            .filter(|part| matches!(part, 1 | 2))
+            .ok_or("Expected history part.part to be 1 or 2.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected history part.nanos to be a number.")?;
+
+        Ok(PartRecord { day, part, nanos })
+    }
+}
+
+impl TryFrom<&JsonValue> for Record {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history record to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|&t| t as u64);
+
+        let commit = json.get("commit").and_then(|v| v.get::<String>()).cloned();
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected history record.profile to be a string.")?;
+
+        let parts = json
+            .get("parts")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected history record.parts to be an array.")?;
+
+        Ok(Record {
+            timestamp,
+            commit,
+            profile: profile.clone(),
+            parts: parts
+                .iter()
+                .map(PartRecord::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::{current_commit, migrate, parse, trend, PartRecord, Record, TREND_LENGTH};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    fn record(commit: &str, profile: &str, nanos: f64) -> Record {
+        Record {
+            timestamp: Some(1_700_000_000),
+            commit: Some(commit.into()),
+            profile: profile.into(),
+            parts: vec![PartRecord {
+                day: day!(14),
+                part: 1,
+                nanos,
+            }],
+        }
+    }
+
+    #[test]
+    fn follows_runs_of_a_profile() {
+        let mut history = vec![record("0123456789", "release", 1.0)];
+        history.push(record("abcdef0123", "release-interpreter", 9.0));
+        for i in 0..TREND_LENGTH {
+            history.push(record("fedcba9876", "release", 2.0 + i as f64));
+        }
+
+        let runs = trend(&history, day!(14), 1, "release");
+        assert_eq!(runs.len(), TREND_LENGTH);
+        assert_eq!(runs.first().unwrap().1, 2.0);
+        assert_eq!(runs.last().unwrap().0.label(), "fedcba9");
+
+        assert!(trend(&history, day!(14), 2, "release").is_empty());
+        assert_eq!(trend(&history[..2], day!(14), 1, "release").len(), 1);
+    }
+
+    #[test]
+    fn skips_truncated_lines() {
+        let line = JsonValue::from(&record("abc", "release", 5.0))
+            .stringify()
+            .unwrap();
+        let history = format!("{line}\n{line}\n{}", &line[..line.len() / 2]);
+
+        assert_eq!(parse(&history), vec![record("abc", "release", 5.0); 2]);
+    }
+
+    #[test]
+    fn migrates_stored_timings() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000 }] }"#;
+        let history = migrate(json);
+
+        assert_eq!(history.len(), 1);
+        let record = &history[0];
+        assert_eq!((record.timestamp, record.commit.as_deref()), (None, None));
+        assert_eq!(record.nanos(day!(1), 1), Some(1_000_000_f64));
+        assert_eq!(record.nanos(day!(1), 2), Some(2_000_000_f64));
+
+        assert!(migrate(r#"{ "data": [] }"#).is_empty());
+    }
+
+    #[test]
+    fn reads_commits_from_git_directories() {
+        let git = env::temp_dir().join(format!("aoc-history-{}", std::process::id()));
+        fs::create_dir_all(git.join("refs/heads")).unwrap();
+
+        fs::write(git.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(
+            git.join("packed-refs"),
+            "# pack-refs\n1111 refs/heads/main\n",
+        )
+        .unwrap();
+        assert_eq!(current_commit(&git), Some("1111".into()));
+
+        fs::write(git.join("refs/heads/main"), "2222\n").unwrap();
+        assert_eq!(current_commit(&git), Some("2222".into()));
+
+        fs::write(git.join("HEAD"), "3333\n").unwrap();
+        assert_eq!(current_commit(&git), Some("3333".into()));
+
+        fs::remove_dir_all(git).unwrap();
+    }
+}
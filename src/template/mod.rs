@@ -2,13 +2,20 @@ use std::{env, fs};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod report;
 pub mod runner;
 
 pub use day::*;
+pub use solution::*;
 
+mod answers;
 mod day;
+mod history;
 mod readme_benchmarks;
+mod regression;
 mod run_multi;
+mod solution;
+mod stats;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -36,7 +43,8 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constant `DAY`, registers the solution as `SOLUTION` and sets up the input and
+/// runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
 #[macro_export]
@@ -59,10 +67,45 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
+        /// The solution of the current day, for running it in-process.
+        pub struct DaySolution;
+
+        impl $crate::template::Solution for DaySolution {
+            fn day(&self) -> $crate::template::Day {
+                DAY
+            }
+
+            fn parts(&self) -> &'static [u8] {
+                &[$($part),*]
+            }
+
+            fn run_part(
+                &self,
+                part: u8,
+                input: &str,
+                options: &$crate::template::runner::RunOptions,
+            ) -> Option<$crate::template::runner::PartOutcome> {
+                $(
+                    if part == $part {
+                        return Some($crate::template::runner::run_part_with(
+                            |input| $crate::template::runner::Part::solve(&$func, input, options),
+                            input,
+                            DAY,
+                            $part,
+                            options,
+                        ));
+                    }
+                )*
+                None
+            }
+        }
+
+        pub static SOLUTION: DaySolution = DaySolution;
+
+        pub fn main() {
             use $crate::template::runner::*;
             let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            run_solution(&SOLUTION, &input, &RunOptions::from_args());
         }
     };
 }
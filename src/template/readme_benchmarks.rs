@@ -8,6 +8,7 @@ use crate::template::Day;
 static MARKER: &str = "<!--- benchmarking table --->";
 
 #[derive(Debug)]
+#[allow(dead_code)]
 pub enum Error {
     Parser(String),
     IO(io::Error),
@@ -68,8 +69,8 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            timing.part_1.map_or_else(|| "-".into(), |t| t.to_string()),
+            timing.part_2.map_or_else(|| "-".into(), |t| t.to_string())
         ));
     }
 
@@ -99,28 +100,58 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day,
+        template::timings::{DayStatus, PartTiming, Timing, Timings},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(PartTiming {
+                        nanos: 10_000_000,
+                        samples: 1,
+                        stats: None,
+                    }),
+                    part_2: Some(PartTiming {
+                        nanos: 20_000_000,
+                        samples: 1,
+                        stats: None,
+                    }),
                     total_nanos: 3e+10,
+                    status: DayStatus::Solved,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(PartTiming {
+                        nanos: 30_000_000,
+                        samples: 1,
+                        stats: None,
+                    }),
+                    part_2: Some(PartTiming {
+                        nanos: 40_000_000,
+                        samples: 1,
+                        stats: None,
+                    }),
                     total_nanos: 7e+10,
+                    status: DayStatus::Solved,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    part_1: Some(PartTiming {
+                        nanos: 40_000_000,
+                        samples: 1,
+                        stats: None,
+                    }),
+                    part_2: Some(PartTiming {
+                        nanos: 50_000_000,
+                        samples: 1,
+                        stats: None,
+                    }),
                     total_nanos: 9e+10,
+                    status: DayStatus::Solved,
                 },
             ],
         }
@@ -168,9 +199,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -0,0 +1,168 @@
+/// Module that compares fresh benchmark results against the stored baseline.
+use std::fmt::Display;
+
+use crate::template::timings::Timings;
+use crate::template::Day;
+
+/// Default slowdown, in percent, above which `cargo time` fails.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// How the mean time of a part changed relative to the baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub day: Day,
+    pub part: u8,
+    /// Mean nanoseconds of the baseline.
+    pub baseline: f64,
+    /// Mean nanoseconds of the fresh run.
+    pub current: f64,
+    /// Whether the difference exceeds the combined 95% confidence intervals.
+    /// `None` if either run was measured without statistics.
+    pub significant: Option<bool>,
+}
+
+impl Change {
+    /// Relative change in percent. Positive values are slowdowns.
+    pub fn percent(&self) -> f64 {
+        (self.current - self.baseline) / self.baseline * 100.0
+    }
+
+    /// Whether the part got slower by more than `threshold` percent. Changes of unknown
+    /// significance count, so that baselines without statistics still guard against regressions.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent() > threshold && self.significant != Some(false)
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let significance = match self.significant {
+            Some(true) => "significant",
+            Some(false) => "within noise",
+            None => "significance unknown",
+        };
+
+        write!(
+            f,
+            "Day {} part {}: {:+.1}% ({:.3}ms → {:.3}ms, {significance})",
+            self.day,
+            self.part,
+            self.percent(),
+            self.baseline / 1_000_000_f64,
+            self.current / 1_000_000_f64,
+        )
+    }
+}
+
+/// Compares every part present in both `baseline` and `current`, in order of `current`.
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<Change> {
+    let mut changes = vec![];
+
+    for timing in &current.data {
+        let Some(previous) = baseline.data.iter().find(|t| t.day == timing.day) else {
+            continue;
+        };
+
+        for part in [1, 2] {
+            let (Some(before), Some(after)) = (previous.part(part), timing.part(part)) else {
+                continue;
+            };
+
+            if before.nanos == 0 {
+                continue;
+            }
+
+            #[allow(clippy::cast_precision_loss)]
+            let (baseline, current) = (before.nanos as f64, after.nanos as f64);
+
+            // Welch-style test: the interval half-widths add up like standard errors.
+            let significant = before
+                .stats
+                .as_ref()
+                .zip(after.stats.as_ref())
+                .map(|(a, b)| (current - baseline).abs() > a.ci_95.hypot(b.ci_95));
+
+            changes.push(Change {
+                day: timing.day,
+                part,
+                baseline,
+                current,
+                significant,
+            });
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare, DEFAULT_THRESHOLD};
+    use crate::day;
+    use crate::template::stats::Stats;
+    use crate::template::timings::{DayStatus, PartTiming, Timing, Timings};
+
+    fn stats(mean: f64, ci_95: f64) -> Stats {
+        Stats {
+            samples: 10,
+            mean,
+            median: mean,
+            min: mean,
+            max: mean,
+            std_dev: 0.0,
+            ci_95,
+            outliers: 0,
+        }
+    }
+
+    fn timings(part_1: Option<Stats>, part_2: Option<u64>) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(14),
+                part_1: part_1.map(PartTiming::from_stats),
+                part_2: part_2.map(|nanos| PartTiming {
+                    nanos,
+                    samples: 0,
+                    stats: None,
+                }),
+                total_nanos: 0.0,
+                status: DayStatus::Solved,
+            }],
+        }
+    }
+
+    #[test]
+    fn catches_significant_regressions() {
+        let baseline = timings(Some(stats(1_000_000.0, 10_000.0)), Some(1_000_000));
+        let current = timings(Some(stats(2_000_000.0, 10_000.0)), Some(1_050_000));
+
+        let changes = compare(&baseline, &current);
+        assert_eq!(changes.len(), 2);
+
+        assert_eq!(changes[0].percent(), 100.0);
+        assert_eq!(changes[0].significant, Some(true));
+        assert!(changes[0].is_regression(DEFAULT_THRESHOLD));
+
+        // stored without statistics.
+        assert!((changes[1].percent() - 5.0).abs() < 1e-9);
+        assert_eq!(changes[1].significant, None);
+        assert!(!changes[1].is_regression(DEFAULT_THRESHOLD));
+    }
+
+    #[test]
+    fn ignores_noise() {
+        let baseline = timings(Some(stats(1_000_000.0, 200_000.0)), None);
+        let current = timings(Some(stats(1_200_000.0, 200_000.0)), None);
+
+        let changes = compare(&baseline, &current);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].significant, Some(false));
+        assert!(!changes[0].is_regression(DEFAULT_THRESHOLD));
+    }
+
+    #[test]
+    fn skips_days_without_baseline() {
+        let current = timings(Some(stats(1_000_000.0, 0.0)), Some(1_000_000));
+        assert!(compare(&Timings::default(), &current).is_empty());
+    }
+}
@@ -0,0 +1,386 @@
+/// Module that renders the outcome of solution parts for scripts and dashboards.
+use std::{
+    collections::HashMap, env, error::Error, fmt::Display, process, str::FromStr, time::Duration,
+};
+use tinyjson::JsonValue;
+
+use crate::template::stats::Stats;
+use crate::template::timings::DayStatus;
+use crate::template::Day;
+
+/// A machine-readable output format, selected with `--format`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// One JSON object per line and part.
+    Json,
+    /// The Test Anything Protocol, version 14.
+    Tap,
+}
+
+impl Format {
+    /// The format passed to the running binary, if any.
+    pub fn from_args() -> Option<Self> {
+        let args: Vec<String> = env::args().collect();
+        let index = args.iter().position(|x| x == "--format")? + 1;
+
+        match args.get(index).map(|format| format.parse()) {
+            Some(Ok(format)) => Some(format),
+            _ => {
+                eprintln!("Unexpected command-line input. Format: --format json|tap");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Json => f.write_str("json"),
+            Format::Tap => f.write_str("tap"),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = FormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "tap" => Ok(Format::Tap),
+            _ => Err(FormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Format`].
+#[derive(Debug)]
+pub struct FormatFromStrError;
+
+impl Error for FormatFromStrError {}
+
+impl Display for FormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting an output format of `json` or `tap`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// How a part of a solution ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    Panicked,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Solved => f.write_str("solved"),
+            Status::Unsolved => f.write_str("unsolved"),
+            Status::Panicked => f.write_str("panicked"),
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "panicked" => Ok(Status::Panicked),
+            _ => Err(format!("unknown part status `{s}`.")),
+        }
+    }
+}
+
+/// The outcome of running a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub day: Day,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    /// Nanoseconds of the single run, or the mean of all samples when benched.
+    pub nanos: f64,
+    pub samples: u64,
+    /// Panic message of a part that panicked.
+    pub error: Option<String>,
+}
+
+impl PartReport {
+    /// Reports a part that ran to completion in `duration`, or in the mean of `stats` if benched.
+    pub fn measured(
+        day: Day,
+        part: u8,
+        answer: Option<String>,
+        duration: Duration,
+        stats: Option<&Stats>,
+    ) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let (nanos, samples) = stats.map_or((duration.as_nanos() as f64, 1), |stats| {
+            (stats.mean, stats.samples)
+        });
+
+        Self {
+            day,
+            part,
+            status: match answer {
+                Some(_) => Status::Solved,
+                None => Status::Unsolved,
+            },
+            answer,
+            nanos,
+            samples,
+            error: None,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        // stringifying values built from numbers and strings cannot fail.
+        JsonValue::from(self).stringify().unwrap()
+    }
+
+    /// Parses a line written by [`to_json`](Self::to_json).
+    pub fn from_json(line: &str) -> Result<Self, String> {
+        let json = JsonValue::from_str(line).map_err(|e| e.to_string())?;
+        Self::try_from(&json)
+    }
+
+    /// A TAP test point, numbered `number` in the stream. Unsolved parts are skipped.
+    pub fn to_tap(&self, number: usize) -> String {
+        let description = format!("{number} - day {} part {}", self.day, self.part);
+
+        let mut lines = vec![match self.status {
+            Status::Solved => format!("ok {description}"),
+            Status::Unsolved => format!("ok {description} # SKIP unsolved"),
+            Status::Panicked => format!("not ok {description}"),
+        }];
+
+        // JSON strings are valid YAML, and keep multi-line answers on one line.
+        let quote = |s: &String| JsonValue::String(s.clone()).stringify().unwrap();
+
+        lines.push("  ---".into());
+        lines.push(format!("  status: {}", self.status));
+        if let Some(answer) = &self.answer {
+            lines.push(format!("  answer: {}", quote(answer)));
+        }
+        lines.push(format!("  nanos: {}", self.nanos));
+        lines.push(format!("  samples: {}", self.samples));
+        if let Some(error) = &self.error {
+            lines.push(format!("  error: {}", quote(error)));
+        }
+        lines.push("  ...".into());
+
+        lines.join("\n")
+    }
+}
+
+/// The outcome of a day whose binary gave no part reports, e.g. because it timed out or did not
+/// build.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayReport {
+    pub day: Day,
+    pub status: DayStatus,
+    /// What the binary printed to stderr before it failed.
+    pub error: Option<String>,
+}
+
+impl DayReport {
+    pub fn to_json(&self) -> String {
+        JsonValue::from(self).stringify().unwrap()
+    }
+
+    /// A TAP test point, numbered `number` in the stream. Days without input are skipped.
+    pub fn to_tap(&self, number: usize) -> String {
+        let description = format!("{number} - day {}", self.day);
+
+        let mut lines = vec![if self.status.is_failure() {
+            format!("not ok {description} # {}", self.status)
+        } else {
+            format!("ok {description} # SKIP {}", self.status)
+        }];
+
+        lines.push("  ---".into());
+        lines.push(format!("  status: {}", self.status));
+        if let Some(error) = &self.error {
+            let error = JsonValue::String(error.clone()).stringify().unwrap();
+            lines.push(format!("  error: {error}"));
+        }
+        lines.push("  ...".into());
+
+        lines.join("\n")
+    }
+}
+
+/// The version line and plan that start a TAP stream of `tests` test points.
+pub fn tap_header(tests: usize) -> String {
+    format!("TAP version 14\n1..{tests}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let string = |s: &Option<String>| s.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        #[allow(clippy::cast_precision_loss)]
+        let map = HashMap::from([
+            ("day".into(), JsonValue::String(value.day.to_string())),
+            ("part".into(), JsonValue::Number(f64::from(value.part))),
+            ("status".into(), JsonValue::String(value.status.to_string())),
+            ("answer".into(), string(&value.answer)),
+            ("nanos".into(), JsonValue::Number(value.nanos)),
+            ("samples".into(), JsonValue::Number(value.samples as f64)),
+            ("error".into(), string(&value.error)),
+        ]);
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&DayReport> for JsonValue {
+    fn from(value: &DayReport) -> Self {
+        let map = HashMap::from([
+            ("day".into(), JsonValue::String(value.day.to_string())),
+            ("status".into(), JsonValue::String(value.status.to_string())),
+            (
+                "error".into(),
+                value
+                    .error
+                    .clone()
+                    .map_or(JsonValue::Null, JsonValue::String),
+            ),
+        ]);
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected report.{key} to be a number."))
+        };
+
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        let status = string("status")
+            .ok_or("Expected report.status to be a string.")?
+            .parse()?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(PartReport {
+            day,
+            part: number("part")? as u8,
+            status,
+            answer: string("answer"),
+            nanos: number("nanos")?,
+            samples: number("samples")? as u64,
+            error: string("error"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DayReport, PartReport, Status};
+    use crate::day;
+    use crate::template::timings::DayStatus;
+
+    fn report(status: Status, answer: Option<&str>, error: Option<&str>) -> PartReport {
+        PartReport {
+            day: day!(14),
+            part: 2,
+            status,
+            answer: answer.map(Into::into),
+            nanos: 1500.0,
+            samples: 40,
+            error: error.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        for report in [
+            report(Status::Solved, Some("##\n.#"), None),
+            report(Status::Unsolved, None, None),
+            report(Status::Panicked, None, Some("attempt to divide by zero")),
+        ] {
+            let json = report.to_json();
+            assert!(!json.contains('\n'));
+            assert_eq!(PartReport::from_json(&json), Ok(report));
+        }
+
+        assert!(PartReport::from_json("Part 1: 42").is_err());
+    }
+
+    #[test]
+    fn renders_tap() {
+        assert_eq!(
+            report(Status::Solved, Some("a\nb"), None).to_tap(3),
+            "ok 3 - day 14 part 2\n  ---\n  status: solved\n  answer: \"a\\nb\"\n  nanos: 1500\n  samples: 40\n  ..."
+        );
+
+        let unsolved = report(Status::Unsolved, None, None).to_tap(1);
+        assert!(unsolved.starts_with("ok 1 - day 14 part 2 # SKIP unsolved\n"));
+
+        let panicked = report(Status::Panicked, None, Some("oops")).to_tap(2);
+        assert!(panicked.starts_with("not ok 2 - day 14 part 2\n"));
+        assert!(panicked.contains("\n  error: \"oops\"\n"));
+    }
+
+    #[test]
+    fn renders_day_reports() {
+        let timed_out = DayReport {
+            day: day!(23),
+            status: DayStatus::TimedOut,
+            error: None,
+        };
+        assert_eq!(
+            timed_out.to_tap(5),
+            "not ok 5 - day 23 # timed out\n  ---\n  status: timed out\n  ..."
+        );
+        let json = timed_out.to_json();
+        assert!(json.contains(r#""status":"timed out""#));
+        assert!(json.contains(r#""error":null"#));
+
+        let panicked = DayReport {
+            day: day!(23),
+            status: DayStatus::Panicked,
+            error: Some("could not open input file".into()),
+        };
+        assert!(panicked
+            .to_tap(1)
+            .contains("\n  error: \"could not open input file\"\n"));
+
+        let unsolved = DayReport {
+            day: day!(23),
+            status: DayStatus::Unsolved,
+            error: None,
+        };
+        assert!(unsolved
+            .to_tap(2)
+            .starts_with("ok 2 - day 23 # SKIP unsolved\n"));
+    }
+}
@@ -1,13 +1,36 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    io,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
 use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::Backend;
 
 use super::{
-    all_days,
-    timings::{Timing, Timings},
+    all_days, registered,
+    report::{tap_header, DayReport, Format, PartReport, Status},
+    runner::RunOptions,
+    timings::{DayStatus, PartTiming, Timing, Timings},
+    Solution,
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+use child_commands::{ChildRun, Exit};
+
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    backend: Backend,
+    timeout: Option<Duration>,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
@@ -24,16 +47,32 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-            if output.is_empty() {
-                println!("Not solved.");
+            // solutions compiled into this binary only match the requested build profile, and
+            // cannot be stopped once they run past the timeout.
+            let solution = registered(day)
+                .filter(|_| timeout.is_none() && is_release == cfg!(not(debug_assertions)));
+
+            let timing = if !Path::new(&get_path_for_bin(day)).exists() {
+                None
+            } else if !get_path_for_input(day).exists() {
+                Some(Timing::new(day, DayStatus::Unsolved))
+            } else if let Some(solution) = solution {
+                Some(run_in_process(solution, is_timed, backend))
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                Some(run_child(day, is_timed, is_release, backend, timeout))
+            };
+
+            match timing {
+                Some(timing) => {
+                    print_status(timing.status, timeout);
+                    timings.push(timing);
+                }
+                None => println!("Not solved."),
             }
         });
 
+    print_summary(&timings);
+
     if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
@@ -46,9 +85,357 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Runs the parts of a registered solution without spawning its binary.
+fn run_in_process(solution: &dyn Solution, is_timed: bool, backend: Backend) -> Timing {
+    let day = solution.day();
+    let mut timing = Timing::new(day, DayStatus::Unsolved);
+
+    let Ok(input) = std::fs::read_to_string(get_path_for_input(day)) else {
+        return timing;
+    };
+
+    let options = RunOptions {
+        timed: is_timed,
+        backend,
+        ..RunOptions::default()
+    };
+
+    for &part in solution.parts() {
+        // like a panicking binary, a panicking part ends its day; the hook prints the message.
+        let Ok(outcome) = panic::catch_unwind(AssertUnwindSafe(|| {
+            solution.run_part(part, &input, &options)
+        })) else {
+            timing.status = DayStatus::Panicked;
+            break;
+        };
+
+        let Some(outcome) = outcome else {
+            continue;
+        };
+
+        if outcome.report.status == Status::Solved {
+            timing.status = DayStatus::Solved;
+        }
+
+        if let Some(stats) = outcome.stats {
+            timing.set_part(part, PartTiming::from_stats(stats));
+        }
+    }
+
+    timing
+}
+
+/// Builds and runs the binary of a day, stopping it after `timeout`.
+fn run_child(
+    day: Day,
+    is_timed: bool,
+    is_release: bool,
+    backend: Backend,
+    timeout: Option<Duration>,
+) -> Timing {
+    // building up front keeps compile errors apart from failures and out of the timeout.
+    let Ok(binary) = child_commands::build_solution(day, is_release) else {
+        return Timing::new(day, DayStatus::BuildFailed);
+    };
+
+    match child_commands::run_solution(&binary, is_timed, None, backend, timeout) {
+        Ok(run) => timing_of(day, &run),
+        Err(e) => {
+            eprintln!("Failed to run day {day}: {e:?}");
+            Timing::new(day, DayStatus::Panicked)
+        }
+    }
+}
+
+/// The timing and status of a day from the output of its binary.
+fn timing_of(day: Day, run: &ChildRun) -> Timing {
+    let mut timing = child_commands::parse_exec_time(&run.lines, day);
+
+    match run.exit {
+        Exit::Success => {}
+        Exit::Failure => timing.status = DayStatus::Panicked,
+        Exit::TimedOut => timing.status = DayStatus::TimedOut,
+    }
+
+    timing
+}
+
+fn print_status(status: DayStatus, timeout: Option<Duration>) {
+    match status {
+        DayStatus::Solved => {}
+        DayStatus::Unsolved => println!("Not solved."),
+        DayStatus::Panicked => println!("{ANSI_BOLD}Panicked.{ANSI_RESET}"),
+        DayStatus::TimedOut => println!(
+            "{ANSI_BOLD}Timed out after {:?}.{ANSI_RESET}",
+            timeout.unwrap_or_default()
+        ),
+        DayStatus::BuildFailed => println!("{ANSI_BOLD}Build failed.{ANSI_RESET}"),
+    }
+}
+
+/// Prints how many days ended with each status, followed by the days that failed.
+fn print_summary(timings: &[Timing]) {
+    if timings.is_empty() {
+        return;
+    }
+
+    let counts = [
+        DayStatus::Solved,
+        DayStatus::Unsolved,
+        DayStatus::Panicked,
+        DayStatus::TimedOut,
+        DayStatus::BuildFailed,
+    ]
+    .into_iter()
+    .filter_map(|status| {
+        let count = timings.iter().filter(|t| t.status == status).count();
+        (count > 0).then(|| format!("{count} {status}"))
+    })
+    .collect::<Vec<_>>();
+
+    println!("\n{ANSI_BOLD}Summary:{ANSI_RESET} {}", counts.join(", "));
+
+    for timing in timings.iter().filter(|t| t.status.is_failure()) {
+        println!("Day {}: {}", timing.day, timing.status);
+    }
+}
+
+/// Runs solutions on up to `jobs` threads at once. The output of each day is buffered and
+/// printed in order of days. Not suited for benchmarks, which would disturb each other.
+pub fn run_multi_parallel(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    jobs: usize,
+    timeout: Option<Duration>,
+) {
+    let binaries = build_once(is_release);
+
+    let days = all_days()
+        .filter(|day| days_to_run.contains(day))
+        .collect::<Vec<_>>();
+
+    let mut timings = vec![];
+    let mut need_space = false;
+
+    run_parallel(
+        &days,
+        jobs,
+        |day| {
+            if !Path::new(&get_path_for_bin(day)).exists() {
+                return None;
+            }
+
+            if !get_path_for_input(day).exists() {
+                return Some((Timing::new(day, DayStatus::Unsolved), None));
+            }
+
+            let Some(binary) = binaries.get(&day) else {
+                return Some((Timing::new(day, DayStatus::BuildFailed), None));
+            };
+
+            match child_commands::run_solution_buffered(binary, timeout) {
+                Ok(run) => Some((timing_of(day, &run), Some(run))),
+                Err(e) => {
+                    eprintln!("Failed to run day {day}: {e:?}");
+                    Some((Timing::new(day, DayStatus::Panicked), None))
+                }
+            }
+        },
+        |day, result| {
+            if need_space {
+                println!();
+            }
+            need_space = true;
+
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("------");
+
+            let Some((timing, run)) = result else {
+                println!("Not solved.");
+                return;
+            };
+
+            if let Some(run) = run {
+                run.lines
+                    .iter()
+                    .filter(|line| !line.starts_with(child_commands::RECORD_MARKER))
+                    .for_each(|line| println!("{line}"));
+                eprint!("{}", run.stderr);
+            }
+
+            print_status(timing.status, timeout);
+            timings.push(timing);
+        },
+    );
+
+    print_summary(&timings);
+}
+
+/// An entry in the stream of [`report_multi`].
+enum Report {
+    Part(PartReport),
+    /// A day that could not report its parts.
+    Day(DayReport),
+}
+
+impl Report {
+    fn to_json(&self) -> String {
+        match self {
+            Report::Part(report) => report.to_json(),
+            Report::Day(report) => report.to_json(),
+        }
+    }
+
+    fn to_tap(&self, number: usize) -> String {
+        match self {
+            Report::Part(report) => report.to_tap(number),
+            Report::Day(report) => report.to_tap(number),
+        }
+    }
+}
+
+/// Runs solutions on up to `jobs` threads at once and prints the reports of all their parts as
+/// one stream in `format`. Days that did not build, have no input, failed or timed out are
+/// reported with their status instead.
+pub fn report_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    format: Format,
+    jobs: usize,
+    timeout: Option<Duration>,
+) {
+    let binaries = build_once(is_release);
+
+    let days = all_days()
+        .filter(|day| days_to_run.contains(day))
+        .collect::<Vec<_>>();
+
+    let mut reports = vec![];
+
+    run_parallel(
+        &days,
+        jobs,
+        // children always report JSON, so that TAP test points can be numbered across days.
+        |day| {
+            let day_report = |status, error| {
+                let report = DayReport { day, status, error };
+                (vec![], Some(report))
+            };
+
+            // skip days that have not been scaffolded yet.
+            if !Path::new(&get_path_for_bin(day)).exists() {
+                return (vec![], None);
+            }
+
+            if !get_path_for_input(day).exists() {
+                return day_report(DayStatus::Unsolved, None);
+            }
+
+            let Some(binary) = binaries.get(&day) else {
+                return day_report(DayStatus::BuildFailed, None);
+            };
+
+            match child_commands::run_solution(
+                binary,
+                false,
+                Some(Format::Json),
+                Backend::default(),
+                timeout,
+            ) {
+                Ok(run) => {
+                    let status = match run.exit {
+                        Exit::Success => None,
+                        Exit::Failure => Some(DayStatus::Panicked),
+                        Exit::TimedOut => Some(DayStatus::TimedOut),
+                    };
+                    let error = Some(run.stderr.trim().to_string()).filter(|e| !e.is_empty());
+                    let report = status.map(|status| DayReport { day, status, error });
+                    (run.lines, report)
+                }
+                Err(e) => day_report(DayStatus::Panicked, Some(format!("{e:?}"))),
+            }
+        },
+        |_, (output, day_report)| {
+            for line in output {
+                match PartReport::from_json(&line) {
+                    Ok(report) => reports.push(Report::Part(report)),
+                    // whatever else a solution prints must not break the stream.
+                    Err(_) => eprintln!("{line}"),
+                }
+            }
+
+            reports.extend(day_report.map(Report::Day));
+        },
+    );
+
+    match format {
+        Format::Json => reports.iter().for_each(|r| println!("{}", r.to_json())),
+        Format::Tap => {
+            println!("{}", tap_header(reports.len()));
+            for (i, report) in reports.iter().enumerate() {
+                println!("{}", report.to_tap(i + 1));
+            }
+        }
+    }
+}
+
+/// Builds all solutions up front, so that their binaries can run at once without waiting on
+/// cargo. Returns the binaries of the days that built.
+pub fn build_once(is_release: bool) -> HashMap<Day, PathBuf> {
+    child_commands::build_solutions(is_release).unwrap_or_else(|e| {
+        eprintln!("Failed to build solutions: {e:?}");
+        HashMap::new()
+    })
+}
+
+/// Calls `run` for every day on up to `jobs` threads. `emit` receives the results on the
+/// calling thread, in order of `days`, as soon as all earlier days are done.
+fn run_parallel<T: Send>(
+    days: &[Day],
+    jobs: usize,
+    run: impl Fn(Day) -> T + Sync,
+    mut emit: impl FnMut(Day, T),
+) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            let (sender, next, run) = (sender.clone(), &next, &run);
+
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(index) else {
+                    break;
+                };
+
+                if sender.send((index, run(day))).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut emitted = 0;
+
+        for (index, result) in receiver {
+            pending.insert(index, result);
+
+            while let Some(result) = pending.remove(&emitted) {
+                emit(days[emitted], result);
+                emitted += 1;
+            }
+        }
+    });
+}
+
 #[derive(Debug)]
+#[allow(dead_code)]
 pub enum Error {
     BrokenPipe,
+    BuildFailed,
     IO(io::Error),
 }
 
@@ -63,129 +450,250 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
+fn get_path_for_input(day: Day) -> PathBuf {
+    PathBuf::from("data/inputs").join(format!("{day}.txt"))
+}
+
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
+    use super::Error;
+    use crate::template::report::{Format, Status};
+    use crate::template::timings::{DayStatus, PartTiming, Timing, TimingRecord};
     use crate::template::Day;
+    use crate::Backend;
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        collections::HashMap,
+        io::{BufRead, BufReader, Read},
+        path::{Path, PathBuf},
+        process::{Child, Command, ExitStatus, Stdio},
+        str::FromStr,
         thread,
+        time::{Duration, Instant},
     };
+    use tinyjson::JsonValue;
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    pub use crate::template::timings::RECORD_MARKER;
+
+    /// How often a running solution is checked against its timeout.
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    /// How a solution binary exited.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Exit {
+        Success,
+        Failure,
+        /// Killed after running past its timeout.
+        TimedOut,
+    }
+
+    /// What a solution binary printed, and how it exited.
+    #[derive(Debug)]
+    pub struct ChildRun {
+        /// Lines printed to stdout, including records.
+        pub lines: Vec<String>,
+        /// Output to stderr, if it was captured rather than forwarded.
+        pub stderr: String,
+        pub exit: Exit,
+    }
+
+    /// Builds every solution bin, continuing past days that fail to build. Returns the
+    /// binaries of the days that built.
+    pub fn build_solutions(is_release: bool) -> Result<HashMap<Day, PathBuf>, Error> {
+        build(&["--bins", "--keep-going"], is_release)
+    }
+
+    /// Builds the solution bin of a day and returns its binary.
+    pub fn build_solution(day: Day, is_release: bool) -> Result<PathBuf, Error> {
+        build(&["--bin", &day.to_string()], is_release)?
+            .remove(&day)
+            .ok_or(Error::BuildFailed)
+    }
+
+    /// Runs `cargo build` and collects the solution binaries it reports. Days that fail to
+    /// build are missing.
+    fn build(targets: &[&str], is_release: bool) -> Result<HashMap<Day, PathBuf>, Error> {
+        let mut args = vec![
+            "build",
+            "--quiet",
+            "--message-format=json-render-diagnostics",
+        ];
+        args.extend(targets);
 
         if is_release {
             args.push("--release");
         }
 
+        let output = Command::new("cargo")
+            .args(&args)
+            .stderr(Stdio::inherit())
+            .output()?;
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(parse_binary)
+            .collect())
+    }
+
+    /// The day and binary of a solution from a `compiler-artifact` message of `cargo build`.
+    fn parse_binary(line: &str) -> Option<(Day, PathBuf)> {
+        let json = JsonValue::from_str(line).ok()?;
+        let json = json.get::<HashMap<String, JsonValue>>()?;
+
+        let name = json
+            .get("target")?
+            .get::<HashMap<String, JsonValue>>()?
+            .get("name")?
+            .get::<String>()?;
+        let day = Day::from_str(name).ok()?;
+
+        let executable = json.get("executable")?.get::<String>()?;
+
+        Some((day, PathBuf::from(executable)))
+    }
+
+    /// Runs a built solution binary, capturing all of its output.
+    pub fn run_solution_buffered(
+        binary: &Path,
+        timeout: Option<Duration>,
+    ) -> Result<ChildRun, Error> {
+        collect(Command::new(binary).arg("--records"), false, timeout)
+    }
+
+    /// Runs a built solution binary. With a `format`, the output is only collected and not
+    /// forwarded to stdout. The binary is killed if it runs for longer than `timeout`.
+    pub fn run_solution(
+        binary: &Path,
+        is_timed: bool,
+        format: Option<Format>,
+        backend: Backend,
+        timeout: Option<Duration>,
+    ) -> Result<ChildRun, Error> {
+        let format_str = format.map(|format| format.to_string());
+        let backend_str = backend.to_string();
+        let mut args = vec![];
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        if backend != Backend::default() {
+            args.extend(["--backend", &backend_str]);
+        }
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        match &format_str {
+            Some(format) => args.extend(["--format", format]),
+            None => args.push("--records"),
+        }
 
-        let mut output = vec![];
+        collect(Command::new(binary).args(&args), format.is_none(), timeout)
+    }
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+    /// Spawns `cmd` and collects its output. When `forward` is set, stdout lines other than
+    /// records and all of stderr are printed as they arrive.
+    pub(super) fn collect(
+        cmd: &mut Command,
+        forward: bool,
+        timeout: Option<Duration>,
+    ) -> Result<ChildRun, Error> {
+        let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+
+        let stdout = BufReader::new(child.stdout.take().ok_or(Error::BrokenPipe)?);
+        let mut stderr = child.stderr.take().ok_or(Error::BrokenPipe)?;
+
+        let stdout_thread = thread::spawn(move || {
+            stdout
+                .lines()
+                .map_while(Result::ok)
+                .inspect(|line| {
+                    if forward && !line.starts_with(RECORD_MARKER) {
+                        println!("{line}");
+                    }
+                })
+                .collect::<Vec<_>>()
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        let stderr_thread = thread::spawn(move || {
+            let mut captured = String::new();
+
+            if forward {
+                BufReader::new(stderr)
+                    .lines()
+                    .map_while(Result::ok)
+                    .for_each(|line| eprintln!("{line}"));
+            } else {
+                let _ = stderr.read_to_string(&mut captured);
+            }
+
+            captured
+        });
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let exit = wait(&mut child, timeout)?;
 
-        Ok(output)
+        Ok(ChildRun {
+            lines: stdout_thread.join().unwrap(),
+            stderr: stderr_thread.join().unwrap(),
+            exit,
+        })
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+    /// Waits for `child` to exit, killing it once `timeout` has passed.
+    fn wait(child: &mut Child, timeout: Option<Duration>) -> Result<Exit, Error> {
+        let exit = |status: ExitStatus| {
+            if status.success() {
+                Exit::Success
+            } else {
+                Exit::Failure
+            }
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        let Some(timeout) = timeout else {
+            return Ok(exit(child.wait()?));
+        };
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        let deadline = Instant::now() + timeout;
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(exit(status));
+            }
 
-                timings.total_nanos += nanos;
-            });
+            if Instant::now() >= deadline {
+                child.kill()?;
+                child.wait()?;
+                return Ok(Exit::TimedOut);
+            }
 
-        timings
+            thread::sleep(POLL_INTERVAL);
+        }
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    /// Collects the records a solution printed when run with `--records`. The day counts as
+    /// solved if any part was.
+    pub fn parse_exec_time(output: &[String], day: Day) -> Timing {
+        let mut timing = Timing::new(day, DayStatus::Unsolved);
+
+        for line in output {
+            let record = match TimingRecord::from_line(line) {
+                None => continue,
+                Some(Ok(record)) => record,
+                Some(Err(e)) => {
+                    eprintln!("Could not parse timing record `{line}`: {e}");
+                    continue;
+                }
+            };
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+            if record.status == Status::Solved {
+                timing.status = DayStatus::Solved;
+            }
+
+            if let Some(stats) = record.stats {
+                timing.set_part(record.part, PartTiming::from_stats(stats));
+            }
+        }
+
+        timing
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -207,35 +715,65 @@ pub mod child_commands {
         use super::parse_exec_time;
 
         use crate::day;
+        use crate::template::report::Status;
+        use crate::template::stats::Stats;
+        use crate::template::timings::{DayStatus, TimingRecord};
+
+        fn record(part: u8, mean: f64, samples: u64) -> String {
+            let stats = Stats {
+                samples,
+                mean,
+                median: mean,
+                min: mean,
+                max: mean,
+                std_dev: 0.0,
+                ci_95: 0.0,
+                outliers: 0,
+            };
+            TimingRecord {
+                part,
+                status: Status::Solved,
+                stats: Some(stats),
+            }
+            .to_string()
+        }
 
         #[test]
         fn parses_execution_times() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "Part 1: 0 (74.1ns @ 100000 samples)".into(),
+                    record(1, 74.13, 100_000),
+                    "Part 2: 10 (74.1ms @ 99999 samples)".into(),
+                    record(2, 74_130_000.0, 99_999),
                     "".into(),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74_130_074_f64);
+            assert_eq!(res.status, DayStatus::Solved);
+
+            let part_1 = res.part_1.unwrap();
+            assert_eq!(part_1.nanos, 74);
+            assert_eq!(part_1.samples, 100_000);
+            assert_eq!(part_1.to_string(), "74.0ns");
+            assert_eq!(res.part_2.unwrap().to_string(), "74.1ms");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
+        fn ignores_patterns_in_output() {
             let res = parse_exec_time(
                 &[
                     "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
+                    "@@aoc-timing".into(),
                     "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    record(2, 100_000_000.0, 1),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.total_nanos, 100_000_000_f64);
+            assert!(res.part_1.is_none());
+            assert_eq!(res.part_2.unwrap().samples, 1);
         }
 
         #[test]
@@ -251,6 +789,94 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.status, DayStatus::Unsolved);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{process::Command, thread, time::Duration};
+
+    use super::child_commands::{collect, parse_exec_time, Exit};
+    use super::run_parallel;
+    use crate::day;
+    use crate::template::report::Status;
+    use crate::template::stats::Stats;
+    use crate::template::timings::{DayStatus, TimingRecord};
+    use crate::template::{all_days, Day};
+
+    #[test]
+    fn emits_parallel_results_in_order() {
+        let days = all_days().take(8).collect::<Vec<_>>();
+        let mut emitted = vec![];
+
+        run_parallel(
+            &days,
+            4,
+            |day| {
+                // early days finish last.
+                let delay = 10 - u64::from(day.into_inner());
+                thread::sleep(Duration::from_millis(delay * 5));
+                day.into_inner() * 2
+            },
+            |day, doubled| emitted.push((day, doubled)),
+        );
+
+        let expected = days
+            .iter()
+            .map(|&day| (day, day.into_inner() * 2))
+            .collect::<Vec<(Day, u8)>>();
+        assert_eq!(emitted, expected);
+    }
+
+    #[test]
+    fn roundtrips_statistics() {
+        let stats = Stats {
+            samples: 10,
+            mean: 2_000_000_000.0,
+            median: 1_999_999_999.5,
+            min: 1_900_000_000.0,
+            max: 2_400_000_000.0,
+            std_dev: 150_000_000.0,
+            ci_95: 107_300_000.0,
+            outliers: 2,
+        };
+        let line = TimingRecord {
+            part: 2,
+            status: Status::Solved,
+            stats: Some(stats.clone()),
         }
+        .to_string();
+
+        let res = parse_exec_time(&[line], day!(1));
+        assert_eq!(res.part_2.unwrap().stats, Some(stats));
+    }
+
+    #[test]
+    fn parses_unsolved_records() {
+        let unsolved = TimingRecord {
+            part: 1,
+            status: Status::Unsolved,
+            stats: None,
+        };
+
+        let res = parse_exec_time(&[unsolved.to_string()], day!(1));
+        assert_eq!(res.status, DayStatus::Unsolved);
+        assert!(res.part_1.is_none());
+    }
+
+    #[test]
+    fn stops_children_after_timeout() {
+        let run = collect(
+            Command::new("sleep").arg("10"),
+            false,
+            Some(Duration::from_millis(50)),
+        )
+        .unwrap();
+        assert_eq!(run.exit, Exit::TimedOut);
+
+        let run = collect(Command::new("false").arg("10"), false, None).unwrap();
+        assert_eq!(run.exit, Exit::Failure);
     }
 }
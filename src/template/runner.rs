@@ -1,25 +1,246 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::process::Output;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::answers;
+use crate::template::report::{tap_header, Format, PartReport, Status};
+use crate::template::stats::{duration, Stats};
+use crate::template::timings::TimingRecord;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, Day, Solution, ANSI_ITALIC, ANSI_RESET};
+use crate::Backend;
+
+/// A tenth of the measured iterations are run beforehand as warmup.
+const WARMUP_DIVISOR: u128 = 10;
+
+/// Number of the last TAP test point printed by this binary.
+static TAP_NUMBER: AtomicUsize = AtomicUsize::new(0);
+
+/// How to run a part. Solution binaries read these from their command line, while solutions
+/// that run in-process are given them explicitly.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Bench the part after running it once.
+    pub timed: bool,
+    /// Print a machine-readable report instead of the human-readable result.
+    pub format: Option<Format>,
+    /// Print a [`TimingRecord`] of each part for the process that runs this binary.
+    pub records: bool,
+    /// Submit the result if `--submit` asks for this part.
+    pub submit: bool,
+    /// Backend that parts run their computers on, see [`Part`].
+    pub backend: Backend,
+}
+
+impl RunOptions {
+    /// The options passed to the running solution binary.
+    pub fn from_args() -> Self {
+        Self {
+            timed: env::args().any(|x| x == "--time"),
+            format: Format::from_args(),
+            records: env::args().any(|x| x == "--records"),
+            submit: true,
+            backend: backend_from_args(),
+        }
+    }
+}
+
+fn backend_from_args() -> Backend {
+    let args: Vec<String> = env::args().collect();
+    let Some(index) = args.iter().position(|x| x == "--backend") else {
+        return Backend::default();
+    };
+
+    match args
+        .get(index + 1)
+        .and_then(|name| Backend::from_name(name))
+    {
+        Some(backend) => backend,
+        None => {
+            eprintln!("Unexpected command-line input. Format: --backend interpreter|compiled");
+            process::exit(1);
+        }
+    }
+}
+
+/// A part of a solution. Parts take their input, and parts that run a computer may also take
+/// the [`Backend`] to run it on, e.g. `fn part_one(input: &str, backend: Backend)`.
+pub trait Part<Args> {
+    type Answer;
+
+    fn solve(&self, input: &str, options: &RunOptions) -> Option<Self::Answer>;
+}
+
+impl<F: Fn(&str) -> Option<T>, T> Part<fn(&str)> for F {
+    type Answer = T;
+
+    fn solve(&self, input: &str, _: &RunOptions) -> Option<T> {
+        self(input)
+    }
+}
+
+impl<F: Fn(&str, Backend) -> Option<T>, T> Part<fn(&str, Backend)> for F {
+    type Answer = T;
+
+    fn solve(&self, input: &str, options: &RunOptions) -> Option<T> {
+        self(input, options.backend)
+    }
+}
+
+/// What running a part produced.
+#[derive(Clone, Debug)]
+pub struct PartOutcome {
+    pub report: PartReport,
+    /// Distribution of the benched runs, if the part was timed and solved.
+    pub stats: Option<Stats>,
+}
 
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+    run_part_with(func, input, day, part, &RunOptions::from_args());
+}
+
+/// Runs a part like [`run_part`], but with explicit `options` instead of the command line.
+pub fn run_part_with<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) -> PartOutcome {
+    if let Some(format) = options.format {
+        return report_part(func, input, day, part, format, options);
+    }
+
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, stats) = run_timed(func, input, options.timed, |result| {
+        print_result(result, &part_str, "");
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+        if options.timed {
+            print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+            let _ = stdout().flush();
+        }
+    });
+
+    let duration_str = match &stats {
+        Some(stats) => format_stats(stats),
+        None => format_duration(&duration),
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    print_result(&result, &part_str, &duration_str);
+
+    let answer = result.map(|result| result.to_string());
+    let stats = stats.filter(|_| answer.is_some());
+    let report = PartReport::measured(day, part, answer, duration, stats.as_ref());
+
+    // a machine-readable copy for `cargo all` and `cargo time`.
+    if options.records {
+        println!(
+            "{}",
+            TimingRecord {
+                part,
+                status: report.status,
+                stats: stats.clone(),
+            }
+        );
     }
+
+    if let (Some(answer), true) = (&report.answer, options.submit) {
+        submit_result(answer, day, part);
+    }
+
+    PartOutcome { report, stats }
+}
+
+/// Runs every part of `solution` on its input, like its binary does.
+pub fn run_solution(
+    solution: &dyn Solution,
+    input: &str,
+    options: &RunOptions,
+) -> Vec<PartOutcome> {
+    if options.format == Some(Format::Tap) {
+        println!("{}", tap_header(solution.parts().len()));
+    }
+
+    let outcomes = solution
+        .parts()
+        .iter()
+        .filter_map(|&part| solution.run_part(part, input, options))
+        .collect();
+
+    // traced computers only collect line hits, so that every program is reported once.
+    #[cfg(feature = "trace")]
+    if let Ok(path) = env::var(crate::TRACE_ENV_VAR) {
+        if let Err(e) = crate::write_hot_spots(&path) {
+            eprintln!("Failed to write hot spots to {path}: {e}");
+        }
+    }
+
+    outcomes
+}
+
+/// Runs a part like [`run_part`], but prints a single [`PartReport`] in `format`.
+fn report_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+    format: Format,
+    options: &RunOptions,
+) -> PartOutcome {
+    // a panicking part is reported like any other, so that the remaining parts still run.
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_timed(&func, input, options.timed, |_| {})
+    }));
+
+    let (report, stats) = match outcome {
+        Ok((result, duration, stats)) => {
+            let answer = result.map(|result| result.to_string());
+            let report = PartReport::measured(day, part, answer, duration, stats.as_ref());
+            (report, stats)
+        }
+        Err(payload) => (
+            PartReport {
+                day,
+                part,
+                status: Status::Panicked,
+                answer: None,
+                nanos: 0.0,
+                samples: 0,
+                error: Some(panic_message(payload.as_ref())),
+            },
+            None,
+        ),
+    };
+
+    match format {
+        Format::Json => println!("{}", report.to_json()),
+        Format::Tap => {
+            let number = TAP_NUMBER.fetch_add(1, Ordering::Relaxed) + 1;
+            println!("{}", report.to_tap(number));
+        }
+    }
+
+    if let (Some(answer), true) = (&report.answer, options.submit) {
+        submit_result(answer, day, part);
+    }
+
+    PartOutcome { report, stats }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".into())
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
@@ -28,8 +249,9 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    timed: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, Option<Stats>) {
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -43,28 +265,19 @@ fn run_timed<I: Clone, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
+    let stats = timed.then(|| bench(func, input, &base_time));
 
-    (result, run.0, run.1)
+    (result, base_time, stats)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Stats {
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    // let caches, branch predictors and the allocator settle before measuring.
+    for _ in 0..cmp::max(bench_iterations / WARMUP_DIVISOR, 1) {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -76,27 +289,31 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Stats::from_samples(&timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_duration(duration: &Duration) -> String {
+    format!(" ({duration:.1?})")
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_stats(stats: &Stats) -> String {
+    let mut str = format!(
+        " ({:.1?} @ {} samples; median {:.1?}, min {:.1?}, max {:.1?}, σ {:.1?}, 95% CI ±{:.1?}",
+        duration(stats.mean),
+        stats.samples,
+        duration(stats.median),
+        duration(stats.min),
+        duration(stats.max),
+        duration(stats.std_dev),
+        duration(stats.ci_95),
+    );
+
+    if stats.is_noisy() {
+        str.push_str(&format!("; ⚠ noisy, {} outliers", stats.outliers));
     }
+
+    str.push(')');
+    str
 }
 
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
@@ -170,5 +387,46 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+    let output = aoc_cli::submit(day, part, &answer);
+
+    if output.as_ref().is_ok_and(aoc_cli::is_accepted) {
+        match answers::store(day, part, &answer) {
+            Ok(()) => println!(
+                "🎄 Recorded answer in \"{}\".",
+                answers::get_path(day, part).display()
+            ),
+            Err(e) => eprintln!("Failed to record answer: {e}"),
+        }
+    }
+
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Part, RunOptions};
+    use crate::Backend;
+
+    fn length(input: &str) -> Option<usize> {
+        Some(input.len())
+    }
+
+    fn backend_name(_: &str, backend: Backend) -> Option<String> {
+        Some(backend.to_string())
+    }
+
+    #[test]
+    fn passes_backend_to_parts() {
+        let options = RunOptions {
+            backend: Backend::Interpreter,
+            ..RunOptions::default()
+        };
+
+        assert_eq!(length.solve("abc", &options), Some(3));
+        assert_eq!(
+            backend_name.solve("abc", &options).as_deref(),
+            Some("interpreter")
+        );
+    }
 }
@@ -0,0 +1,71 @@
+/// Module that lets a single binary run any day's solution in-process.
+use std::sync::OnceLock;
+
+use crate::template::runner::{PartOutcome, RunOptions};
+use crate::template::Day;
+
+/// A day's solution. Implemented for every day by the [`solution!`](crate::solution) macro.
+pub trait Solution: Sync {
+    fn day(&self) -> Day;
+
+    /// The parts the solution runs, in order.
+    fn parts(&self) -> &'static [u8];
+
+    /// Runs a part on `input` and prints its result, like the day's binary would.
+    /// Returns `None` if the solution does not run this part.
+    fn run_part(&self, part: u8, input: &str, options: &RunOptions) -> Option<PartOutcome>;
+}
+
+static REGISTRY: OnceLock<&'static [&'static dyn Solution]> = OnceLock::new();
+
+/// Makes `solutions` available to [`registered`]. Only the first registration counts.
+pub fn register(solutions: &'static [&'static dyn Solution]) {
+    let _ = REGISTRY.set(solutions);
+}
+
+/// The registered solution of a day, if the running binary was built with it.
+pub fn registered(day: Day) -> Option<&'static dyn Solution> {
+    REGISTRY
+        .get()?
+        .iter()
+        .find(|solution| solution.day() == day)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{register, registered, Solution};
+    use crate::day;
+    use crate::template::runner::{run_part_with, PartOutcome, RunOptions};
+    use crate::template::Day;
+
+    struct Doubling;
+
+    impl Solution for Doubling {
+        fn day(&self) -> Day {
+            day!(7)
+        }
+
+        fn parts(&self) -> &'static [u8] {
+            &[2]
+        }
+
+        fn run_part(&self, part: u8, input: &str, options: &RunOptions) -> Option<PartOutcome> {
+            let double = |input: &str| input.parse::<u32>().ok().map(|n| n * 2);
+            (part == 2).then(|| run_part_with(double, input, self.day(), part, options))
+        }
+    }
+
+    #[test]
+    fn runs_registered_solutions() {
+        register(&[&Doubling]);
+
+        assert!(registered(day!(8)).is_none());
+
+        let solution = registered(day!(7)).unwrap();
+        let outcome = solution.run_part(2, "21", &RunOptions::default()).unwrap();
+        assert_eq!(outcome.report.answer.as_deref(), Some("42"));
+        assert!(outcome.stats.is_none());
+        assert!(solution.run_part(1, "21", &RunOptions::default()).is_none());
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+/// Samples outside of `IQR_FENCE` interquartile ranges around the quartiles count as outliers.
+const IQR_FENCE: f64 = 1.5;
+
+/// A benchmark is noisy if more than this share of its samples are outliers.
+const NOISY_OUTLIER_RATIO: f64 = 0.1;
+
+/// Two-sided 97.5% quantiles of Student's t-distribution for 1 to 30 degrees of freedom.
+const T_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+/// Summary statistics of benchmark samples. All durations are in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: u64,
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub std_dev: f64,
+    /// Half-width of the 95% confidence interval of the mean.
+    pub ci_95: f64,
+    /// Samples outside Tukey's fences.
+    pub outliers: u64,
+}
+
+impl Stats {
+    /// Summarizes at least one sample.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos = samples
+            .iter()
+            .map(|d| d.as_nanos() as f64)
+            .collect::<Vec<_>>();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        #[allow(clippy::cast_precision_loss)]
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+
+        let std_dev = if nanos.len() > 1 {
+            (nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+
+        let t = T_95
+            .get(nanos.len().saturating_sub(2))
+            .copied()
+            .unwrap_or(1.96);
+
+        let (q1, q3) = (quantile(&nanos, 0.25), quantile(&nanos, 0.75));
+        let fence = IQR_FENCE * (q3 - q1);
+        let outliers = nanos
+            .iter()
+            .filter(|&&x| x < q1 - fence || x > q3 + fence)
+            .count();
+
+        Self {
+            samples: nanos.len() as u64,
+            mean,
+            median: quantile(&nanos, 0.5),
+            min: nanos[0],
+            max: nanos[nanos.len() - 1],
+            std_dev,
+            ci_95: t * std_dev / n.sqrt(),
+            outliers: outliers as u64,
+        }
+    }
+
+    /// Whether so many samples are outliers that the mean is not to be trusted.
+    pub fn is_noisy(&self) -> bool {
+        #[allow(clippy::cast_precision_loss)]
+        let ratio = self.outliers as f64 / self.samples as f64;
+        ratio > NOISY_OUTLIER_RATIO
+    }
+}
+
+/// Converts nanoseconds, as stored in [`Stats`], back to a [`Duration`].
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/// Linearly interpolated quantile of sorted values.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let position = q * (sorted.len() - 1) as f64;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+
+    #[allow(clippy::cast_precision_loss)]
+    let fraction = position - lower as f64;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * fraction
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    fn stats(nanos: &[u64]) -> Stats {
+        let samples = nanos
+            .iter()
+            .map(|&n| Duration::from_nanos(n))
+            .collect::<Vec<_>>();
+        Stats::from_samples(&samples)
+    }
+
+    #[test]
+    fn summarizes_samples() {
+        let stats = stats(&[40, 10, 30, 20]);
+
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.mean, 25.0);
+        assert_eq!(stats.median, 25.0);
+        assert_eq!((stats.min, stats.max), (10.0, 40.0));
+        assert!((stats.std_dev - 12.909_944).abs() < 1e-6);
+        assert!((stats.ci_95 - 3.182 * 12.909_944 / 2.0).abs() < 1e-3);
+        assert_eq!(stats.outliers, 0);
+        assert!(!stats.is_noisy());
+    }
+
+    #[test]
+    fn flags_noisy_samples() {
+        let mut nanos = vec![100; 18];
+        nanos.extend([1_000, 5_000]);
+
+        let stats = stats(&nanos);
+        assert_eq!(stats.outliers, 2);
+        assert_eq!(stats.median, 100.0);
+        assert!(!stats.is_noisy());
+
+        nanos.push(9_000);
+        assert!(self::stats(&nanos).is_noisy());
+    }
+
+    #[test]
+    fn handles_single_samples() {
+        let stats = stats(&[7]);
+        assert_eq!((stats.mean, stats.median, stats.std_dev), (7.0, 7.0, 0.0));
+    }
+}
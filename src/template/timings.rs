@@ -1,17 +1,170 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
+use crate::template::report::Status;
+use crate::template::stats::Stats;
 use crate::template::Day;
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+pub(super) static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+
+/// Benchmark time of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    /// Mean nanoseconds per run, rounded.
+    pub nanos: u64,
+    /// Number of measured runs. Zero for timings stored as formatted strings without it.
+    pub samples: u64,
+    /// Distribution of the measured runs. Unknown for timings stored as formatted strings.
+    pub stats: Option<Stats>,
+}
+
+impl PartTiming {
+    pub fn from_stats(stats: Stats) -> Self {
+        Self {
+            nanos: round_nanos(stats.mean),
+            samples: stats.samples,
+            stats: Some(stats),
+        }
+    }
+}
+
+impl Display for PartTiming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.1?}", Duration::from_nanos(self.nanos))
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn round_nanos(nanos: f64) -> u64 {
+    nanos.round() as u64
+}
+
+/// How a day ended when run by `cargo all` or `cargo time`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DayStatus {
+    /// At least one part gave an answer.
+    #[default]
+    Solved,
+    /// No part gave an answer, or the day has no input.
+    Unsolved,
+    /// A part panicked, or the binary exited with an error.
+    Panicked,
+    /// The day ran longer than `--timeout` and was stopped.
+    TimedOut,
+    BuildFailed,
+}
+
+impl DayStatus {
+    pub fn is_failure(self) -> bool {
+        matches!(
+            self,
+            DayStatus::Panicked | DayStatus::TimedOut | DayStatus::BuildFailed
+        )
+    }
+}
+
+impl Display for DayStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayStatus::Solved => f.write_str("solved"),
+            DayStatus::Unsolved => f.write_str("unsolved"),
+            DayStatus::Panicked => f.write_str("panicked"),
+            DayStatus::TimedOut => f.write_str("timed out"),
+            DayStatus::BuildFailed => f.write_str("build failed"),
+        }
+    }
+}
+
+impl FromStr for DayStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(DayStatus::Solved),
+            "unsolved" => Ok(DayStatus::Unsolved),
+            "panicked" => Ok(DayStatus::Panicked),
+            "timed out" => Ok(DayStatus::TimedOut),
+            "build failed" => Ok(DayStatus::BuildFailed),
+            _ => Err(format!("unknown day status `{s}`.")),
+        }
+    }
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
+    pub status: DayStatus,
+}
+
+impl Timing {
+    /// A day without timed parts.
+    pub fn new(day: Day, status: DayStatus) -> Self {
+        Self {
+            day,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            status,
+        }
+    }
+
+    /// Sets the timing of a part, adding its mean to the total.
+    pub fn set_part(&mut self, part: u8, timing: PartTiming) {
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = timing.nanos as f64;
+        self.total_nanos += nanos;
+
+        match part {
+            1 => self.part_1 = Some(timing),
+            _ => self.part_2 = Some(timing),
+        }
+    }
+
+    pub fn part(&self, part: u8) -> Option<&PartTiming> {
+        match part {
+            1 => self.part_1.as_ref(),
+            _ => self.part_2.as_ref(),
+        }
+    }
+}
+
+/// How a part ended and how long it took, as reported by a solution binary run with
+/// `--records`: one line holding [`RECORD_MARKER`] followed by a JSON object.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingRecord {
+    pub part: u8,
+    pub status: Status,
+    /// Distribution of the benched runs, if run with `--time` and solved.
+    pub stats: Option<Stats>,
+}
+
+/// Prefix that tells timing records apart from the rest of a solution's output.
+pub const RECORD_MARKER: &str = "@@aoc-timing ";
+
+impl TimingRecord {
+    /// Parses a line of output. Returns `None` for lines that are not timing records.
+    pub fn from_line(line: &str) -> Option<Result<Self, String>> {
+        let json = line.strip_prefix(RECORD_MARKER)?;
+
+        Some(
+            JsonValue::from_str(json)
+                .map_err(|e| e.to_string())
+                .and_then(|json| Self::try_from(&json)),
+        )
+    }
+}
+
+impl Display for TimingRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = JsonValue::from(self)
+            .stringify()
+            .map_err(|_| std::fmt::Error)?;
+        write!(f, "{RECORD_MARKER}{json}")
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -45,11 +198,18 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Days of `other` that failed or were not solved, e.g. for lack of an input, do not
+    /// overwrite a stored timing.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let stored = self.data.iter().find(|t| t.day == timing.day);
+
+            match stored {
+                Some(stored) if timing.status != DayStatus::Solved => data.push(stored.clone()),
+                _ => data.push(timing.clone()),
+            }
         }
 
         for timing in &self.data {
@@ -58,7 +218,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -120,25 +280,14 @@ impl From<&Timing> for JsonValue {
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+        map.insert("status".into(), JsonValue::String(value.status.to_string()));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
-
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        for (key, part) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                part.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
 
         JsonValue::Object(map)
     }
@@ -158,26 +307,224 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
-
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let part = |key: &str| -> Result<Option<PartTiming>, String> {
+            let value = json
+                .get(key)
+                .ok_or(format!("Expected timing.{key} to be present."))?;
+
+            match value {
+                JsonValue::Null => Ok(None),
+                // timings used to be stored as formatted strings, next to optional statistics.
+                JsonValue::String(formatted) => {
+                    let nanos = parse_duration(formatted)
+                        .ok_or(format!("Expected timing.{key} to be a duration."))?;
+                    let stats = match json.get(&format!("{key}_stats")) {
+                        None | Some(JsonValue::Null) => None,
+                        Some(v) => Some(Stats::try_from(v)?),
+                    };
+                    Ok(Some(PartTiming {
+                        nanos: round_nanos(nanos),
+                        samples: stats.as_ref().map_or(0, |stats| stats.samples),
+                        stats,
+                    }))
+                }
+                value => PartTiming::try_from(value).map(Some),
+            }
+        };
+
+        // timings stored before statuses existed only held days that ran.
+        let status = match json.get("status") {
+            None => DayStatus::Solved,
+            Some(v) => v
+                .get::<String>()
+                .ok_or("Expected timing.status to be a string.")?
+                .parse()?,
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
             total_nanos,
+            status,
+        })
+    }
+}
+
+/// Parses a formatted [`Duration`](std::time::Duration) into nanoseconds.
+fn parse_duration(s: &str) -> Option<f64> {
+    let parse_to_float = |s: &str, postfix: &str| s.split(postfix).next()?.parse::<f64>().ok();
+
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let map = HashMap::from([
+            ("nanos".into(), JsonValue::Number(value.nanos as f64)),
+            ("samples".into(), JsonValue::Number(value.samples as f64)),
+            (
+                "stats".into(),
+                value
+                    .stats
+                    .as_ref()
+                    .map_or(JsonValue::Null, JsonValue::from),
+            ),
+        ]);
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected part timing.nanos to be a number.")?;
+
+        let stats = match json.get("stats") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(Stats::try_from(v)?),
+        };
+
+        // part timings stored before the sample count was a field only had it in their stats.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = match json.get("samples") {
+            None => stats.as_ref().map_or(0, |stats| stats.samples),
+            Some(v) => v
+                .get::<f64>()
+                .map(|&samples| samples as u64)
+                .ok_or("Expected part timing.samples to be a number.")?,
+        };
+
+        Ok(PartTiming {
+            nanos: round_nanos(nanos),
+            samples,
+            stats,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&TimingRecord> for JsonValue {
+    fn from(value: &TimingRecord) -> Self {
+        let map = HashMap::from([
+            ("part".into(), JsonValue::Number(f64::from(value.part))),
+            ("status".into(), JsonValue::String(value.status.to_string())),
+            (
+                "stats".into(),
+                value
+                    .stats
+                    .as_ref()
+                    .map_or(JsonValue::Null, JsonValue::from),
+            ),
+        ]);
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for TimingRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing record to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|&part| part as u8)
+            .filter(|part| matches!(part, 1 | 2))
+            .ok_or("Expected timing record.part to be 1 or 2.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected timing record.status to be a string.")?
+            .parse()?;
+
+        let stats = match json.get("stats") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(Stats::try_from(v)?),
+        };
+
+        Ok(TimingRecord {
+            part,
+            status,
+            stats,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let map = HashMap::from([
+            ("samples".into(), JsonValue::Number(value.samples as f64)),
+            ("mean".into(), JsonValue::Number(value.mean)),
+            ("median".into(), JsonValue::Number(value.median)),
+            ("min".into(), JsonValue::Number(value.min)),
+            ("max".into(), JsonValue::Number(value.max)),
+            ("std_dev".into(), JsonValue::Number(value.std_dev)),
+            ("ci_95".into(), JsonValue::Number(value.ci_95)),
+            ("outliers".into(), JsonValue::Number(value.outliers as f64)),
+        ]);
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected timing stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Stats {
+            samples: number("samples")? as u64,
+            mean: number("mean")?,
+            median: number("median")?,
+            min: number("min")?,
+            max: number("max")?,
+            std_dev: number("std_dev")?,
+            ci_95: number("ci_95")?,
+            outliers: number("outliers")? as u64,
         })
     }
 }
@@ -188,46 +535,127 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{DayStatus, PartTiming, Timing, Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(PartTiming {
+                        nanos: 10_000_000,
+                        samples: 1,
+                        stats: None,
+                    }),
+                    part_2: Some(PartTiming {
+                        nanos: 20_000_000,
+                        samples: 1,
+                        stats: None,
+                    }),
                     total_nanos: 3e+10,
+                    status: DayStatus::Solved,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(PartTiming {
+                        nanos: 30_000_000,
+                        samples: 1,
+                        stats: None,
+                    }),
+                    part_2: Some(PartTiming {
+                        nanos: 40_000_000,
+                        samples: 1,
+                        stats: None,
+                    }),
                     total_nanos: 7e+10,
+                    status: DayStatus::Solved,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    part_1: Some(PartTiming {
+                        nanos: 40_000_000,
+                        samples: 1,
+                        stats: None,
+                    }),
                     part_2: None,
                     total_nanos: 4e+10,
+                    status: DayStatus::Solved,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{DayStatus, PartTiming, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "nanos": 1234567, "samples": 50, "stats": null }, "part_2": null, "total_nanos": 1234567 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(
+                timing.part_1,
+                Some(PartTiming {
+                    nanos: 1_234_567,
+                    samples: 50,
+                    stats: None
+                })
+            );
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.total_nanos, 1_234_567_f64);
+        }
+
+        #[test]
+        fn handles_json_timings_without_samples() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "nanos": 1234567.5, "stats": { "samples": 10, "mean": 1234567.5, "median": 1234567.5, "min": 1234567.5, "max": 1234567.5, "std_dev": 0, "ci_95": 0, "outliers": 0 } }, "part_2": { "nanos": 5, "stats": null }, "total_nanos": 1234572.5 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!((part_1.nanos, part_1.samples), (1_234_568, 10));
+            let part_2 = timing.part_2.as_ref().unwrap();
+            assert_eq!((part_2.nanos, part_2.samples), (5, 0));
+        }
+
+        #[test]
+        fn handles_day_statuses() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": null, "total_nanos": 0, "status": "timed out" }, { "day": "02", "part_1": null, "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].status, DayStatus::TimedOut);
+            assert_eq!(timings.data[1].status, DayStatus::Solved);
+
+            let roundtrip =
+                Timings::try_from(tinyjson::JsonValue::from(timings).stringify().unwrap()).unwrap();
+            assert_eq!(roundtrip.data[0].status, DayStatus::TimedOut);
+        }
+
+        #[test]
+        fn handles_formatted_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "1.5µs", "total_nanos": 1001500 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1.as_ref().unwrap().nanos, 1_000_000);
+            assert_eq!(timing.part_2.as_ref().unwrap().nanos, 1_500);
+            assert_eq!(timing.part_2.as_ref().unwrap().samples, 0);
+        }
+
+        #[test]
+        fn handles_formatted_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 10, "mean": 1000000, "median": 900000, "min": 800000, "max": 1500000, "std_dev": 200000, "ci_95": 143000, "outliers": 1 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.samples, 10);
+            let stats = part_1.stats.as_ref().unwrap();
+            assert_eq!(stats.median, 900_000_f64);
+            assert_eq!(stats.outliers, 1);
+            assert!(timing.part_2.is_none());
         }
 
         #[test]
@@ -278,7 +706,7 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{DayStatus, PartTiming, Timing, Timings},
         };
 
         #[test]
@@ -286,13 +714,22 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: Some(PartTiming {
+                        nanos: 1_000_000,
+                        samples: 1,
+                        stats: None,
+                    }),
+                    part_2: Some(PartTiming {
+                        nanos: 2_000_000,
+                        samples: 1,
+                        stats: None,
+                    }),
                     total_nanos: 3_000_000_000_f64,
+                    status: DayStatus::Solved,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -300,13 +737,18 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    part_1: Some(PartTiming {
+                        nanos: 1_000_000,
+                        samples: 1,
+                        stats: None,
+                    }),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    status: DayStatus::Solved,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -317,17 +759,18 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    status: DayStatus::Solved,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{DayStatus, Timing, Timings},
         };
 
         use super::get_mock_timings;
@@ -341,6 +784,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    status: DayStatus::Solved,
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,6 +805,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    status: DayStatus::Solved,
                 }],
             };
             let merged = timings.merge(&other);
@@ -372,6 +817,38 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_stored_timings_of_failed_days() {
+            let timings = get_mock_timings();
+
+            let other = Timings {
+                data: vec![
+                    Timing::new(day!(2), DayStatus::TimedOut),
+                    Timing::new(day!(3), DayStatus::Panicked),
+                ],
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[1].total_nanos, 7e+10);
+            assert_eq!(merged.data[1].status, DayStatus::Solved);
+            assert_eq!(merged.data[2].status, DayStatus::Panicked);
+        }
+
+        #[test]
+        fn keeps_stored_timings_of_unsolved_days() {
+            let timings = get_mock_timings();
+
+            let other = Timings {
+                data: vec![Timing::new(day!(2), DayStatus::Unsolved)],
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data.len(), 3);
+            assert_eq!(merged.data[1].total_nanos, 7e+10);
+            assert_eq!(merged.data[1].status, DayStatus::Solved);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();